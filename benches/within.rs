@@ -0,0 +1,92 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Baseline timings of `within`, run with `cargo bench`
+
+use std::f64::consts::PI;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use geo_raycasting::RayCasting;
+use geo_types::{Coord, LineString};
+
+fn p(x: f64, y: f64) -> Coord<f64> {
+    Coord { x, y }
+}
+
+fn poly_square() -> LineString<f64> {
+    vec![
+        (0.0, 0.0),
+        (10.0, 0.0),
+        (10.0, 10.0),
+        (0.0, 10.0),
+        (0.0, 0.0),
+    ]
+    .into()
+}
+
+fn poly_hexagon() -> LineString<f64> {
+    vec![
+        (3.0, 0.0),
+        (7.0, 0.0),
+        (10.0, 5.0),
+        (7.0, 10.0),
+        (3.0, 10.0),
+        (0.0, 5.0),
+        (3.0, 0.0),
+    ]
+    .into()
+}
+
+/// Star shaped ring with alternating radii, centered on (5, 5)
+fn poly_large(vertices: usize) -> LineString<f64> {
+    (0..vertices)
+        .map(|i| {
+            let angle = 2. * PI * i as f64 / vertices as f64;
+            let radius = if i % 2 == 0 { 5. } else { 4. };
+            (5. + radius * angle.cos(), 5. + radius * angle.sin())
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// Times `within` for a point inside `ring` and one outside, which should lie within the bounding
+/// box to time the ray cast rather than the bounding box rejection
+fn bench_ring(
+    c: &mut Criterion,
+    name: &str,
+    ring: &LineString<f64>,
+    inside: Coord<f64>,
+    outside: Coord<f64>,
+) {
+    assert!(ring.within(&inside));
+    assert!(!ring.within(&outside));
+    c.bench_function(&format!("{} inside", name), |b| {
+        b.iter(|| black_box(ring).within(black_box(&inside)))
+    });
+    c.bench_function(&format!("{} outside", name), |b| {
+        b.iter(|| black_box(ring).within(black_box(&outside)))
+    });
+}
+
+fn within(c: &mut Criterion) {
+    // the square fills its bounding box, every point outside is rejected without a ray cast
+    bench_ring(c, "poly_square", &poly_square(), p(5.0, 8.0), p(20.0, 5.0));
+    // in the corner region cut off by the hexagon
+    bench_ring(c, "poly_hexagon", &poly_hexagon(), p(5.0, 8.0), p(0.5, 0.5));
+    // in the notch between the first two spikes of the star
+    let notch = PI / 1000.;
+    bench_ring(
+        c,
+        "poly_large_1000",
+        &poly_large(1000),
+        p(5.0, 5.5),
+        p(5. + 4.9 * notch.cos(), 5. + 4.9 * notch.sin()),
+    );
+}
+
+criterion_group!(benches, within);
+criterion_main!(benches);
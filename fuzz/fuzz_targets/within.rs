@@ -0,0 +1,58 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Fuzzes `within` with arbitrary polygons and points, looking for panics and endless loops
+//!
+//! Raw `f64`s already cover NaN, infinities and the extremes, and duplicate vertices are forced
+//! in on top of them. Run with `cargo fuzz run within` from this directory.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use geo_raycasting::{PreparedPolygon, RayCasting, RobustRayCasting};
+use geo_types::{Coord, LineString, Polygon};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    exterior: Vec<(f64, f64)>,
+    interiors: Vec<Vec<(f64, f64)>>,
+    duplicates: Vec<u8>,
+    point: (f64, f64),
+}
+
+fn ring(mut coords: Vec<(f64, f64)>, duplicates: &[u8]) -> LineString<f64> {
+    for &index in duplicates {
+        if let Some(&coord) = coords.get(usize::from(index)) {
+            coords.insert(usize::from(index), coord);
+        }
+    }
+    coords.into()
+}
+
+fuzz_target!(|input: Input| {
+    let Input {
+        exterior,
+        interiors,
+        duplicates,
+        point,
+    } = input;
+    let poly = Polygon::new(
+        ring(exterior, &duplicates),
+        interiors
+            .into_iter()
+            .map(|coords| ring(coords, &duplicates))
+            .collect(),
+    );
+    let pt = Coord::from(point);
+
+    // only termination matters here, the results of non-finite inputs are unspecified
+    let _ = poly.within(&pt);
+    let _ = poly.exterior().within(&pt);
+    let _ = poly.within_robust(&pt);
+    let _ = PreparedPolygon::new(&poly).within(&pt);
+});
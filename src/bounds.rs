@@ -0,0 +1,83 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Bounding boxes of polygonal areas
+
+use geo_types::{CoordFloat, LineString, Polygon, Rect};
+
+use crate::bounding_rect;
+
+/// Trait computing the smallest axis-aligned rectangle enclosing a polygonal area
+///
+/// This is the box used to reject far away points before casting the ray, a point outside of it,
+/// as reported by `Rect`'s `RayCasting::within`, is never within the area.
+pub trait BoundingRect<T: CoordFloat> {
+    /// Bounding box of the coordinates, `None` when there are none
+    fn bounding_rect(&self) -> Option<Rect<T>>;
+}
+
+impl<T: CoordFloat> BoundingRect<T> for LineString<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        bounding_rect(self.coords())
+    }
+}
+
+/// Only the exterior ring is considered, holes lying within it
+impl<T: CoordFloat> BoundingRect<T> for Polygon<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        self.exterior().bounding_rect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoundingRect;
+
+    use geo_types::{LineString, Polygon, Rect};
+
+    fn square() -> LineString<f64> {
+        vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn bounding_rect_square() {
+        let poly_square = square();
+        assert_eq!(
+            poly_square.bounding_rect(),
+            Some(Rect::new((0.0, 0.0), (10.0, 10.0)))
+        );
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![vec![(2.5, 2.5), (7.5, 2.5), (7.5, 7.5), (2.5, 7.5)].into()],
+        );
+        assert_eq!(
+            poly_square_hole.bounding_rect(),
+            Some(Rect::new((0.0, 0.0), (10.0, 10.0)))
+        );
+        let triangle: LineString<f64> = vec![(-1.0, 3.0), (4.0, -2.0), (2.0, 8.0)].into();
+        assert_eq!(
+            triangle.bounding_rect(),
+            Some(Rect::new((-1.0, -2.0), (4.0, 8.0)))
+        );
+    }
+
+    #[test]
+    fn bounding_rect_empty() {
+        assert_eq!(LineString::<f64>(vec![]).bounding_rect(), None);
+        assert_eq!(
+            Polygon::<f64>::new(LineString(vec![]), vec![]).bounding_rect(),
+            None
+        );
+    }
+}
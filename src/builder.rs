@@ -0,0 +1,134 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Validating polygon construction
+
+use alloc::vec::Vec;
+
+use geo_types::{Coord, CoordFloat, Polygon};
+
+use crate::{check_exterior, check_finite, ring_from_points, RayCastError, Validation};
+
+/// Incremental builder of a polygon with no holes, validating it before handing it out
+///
+/// ```
+/// use geo_raycasting::{PolygonBuilder, RayCasting};
+///
+/// let mut builder = PolygonBuilder::new();
+/// for corner in [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)] {
+///     builder.push(corner.into());
+/// }
+/// let poly = builder.build().unwrap();
+/// assert!(poly.exterior().is_closed());
+/// assert!(poly.within(&(5.0, 5.0)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolygonBuilder<T: CoordFloat> {
+    coords: Vec<Coord<T>>,
+}
+
+impl<T: CoordFloat> PolygonBuilder<T> {
+    /// Empty builder
+    pub fn new() -> Self {
+        PolygonBuilder { coords: Vec::new() }
+    }
+
+    /// Appends a vertex to the exterior ring
+    pub fn push(&mut self, c: Coord<T>) {
+        self.coords.push(c);
+    }
+
+    /// Closes the exterior ring, when it isn't already, and checks it's fit for ray casting
+    ///
+    /// Fails with `NonFiniteCoordinate` on NaN or infinite vertices, `EmptyRing` when no vertex
+    /// was pushed, `DegeneratePolygon` when fewer than 3 distinct vertices, or only collinear
+    /// ones, were pushed, and `SelfIntersection` when the ring isn't simple, see
+    /// `Validation::is_simple`.
+    pub fn build(self) -> Result<Polygon<T>, RayCastError> {
+        check_finite(&self.coords)?;
+        let exterior = ring_from_points(self.coords);
+        check_exterior(&exterior)?;
+        if !exterior.is_simple() {
+            return Err(RayCastError::SelfIntersection);
+        }
+        Ok(Polygon::new(exterior, Vec::new()))
+    }
+}
+
+impl<T: CoordFloat> Default for PolygonBuilder<T> {
+    fn default() -> Self {
+        PolygonBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PolygonBuilder;
+    use crate::{RayCastError, RayCasting};
+
+    use geo_types::Coord;
+
+    fn build(coords: &[(f64, f64)]) -> Result<geo_types::Polygon<f64>, RayCastError> {
+        let mut builder = PolygonBuilder::new();
+        for &c in coords {
+            builder.push(Coord::from(c));
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn build_square() {
+        let poly = build(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]).unwrap();
+        assert_eq!(poly.exterior().0.len(), 5);
+        assert!(poly.within(&(5.0, 5.0)));
+        let closed = build(&[
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]);
+        assert_eq!(closed.unwrap(), poly);
+    }
+
+    #[test]
+    fn build_invalid() {
+        let poly_strange = [
+            (0.0, 0.0),
+            (2.5, 2.5),
+            (0.0, 10.0),
+            (2.5, 7.5),
+            (7.5, 7.5),
+            (10.0, 10.0),
+            (10.0, 0.0),
+            (2.5, 2.5),
+        ];
+        assert!(matches!(
+            build(&poly_strange),
+            Err(RayCastError::SelfIntersection)
+        ));
+        let bowtie = [(0.0, 0.0), (10.0, 10.0), (10.0, 0.0), (0.0, 10.0)];
+        assert!(matches!(
+            build(&bowtie),
+            Err(RayCastError::SelfIntersection)
+        ));
+
+        assert!(matches!(build(&[]), Err(RayCastError::EmptyRing)));
+        assert!(matches!(
+            build(&[(0.0, 0.0), (10.0, 0.0)]),
+            Err(RayCastError::DegeneratePolygon)
+        ));
+        assert!(matches!(
+            build(&[(0.0, 0.0), (5.0, 5.0), (10.0, 10.0)]),
+            Err(RayCastError::DegeneratePolygon)
+        ));
+        assert!(matches!(
+            build(&[(0.0, 0.0), (10.0, f64::NAN), (0.0, 10.0)]),
+            Err(RayCastError::NonFiniteCoordinate)
+        ));
+    }
+}
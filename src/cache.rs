@@ -0,0 +1,195 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Memoization of repeated containment queries
+
+use alloc::collections::{BTreeMap, VecDeque};
+use core::cell::RefCell;
+
+use geo_types::{Coord, CoordFloat};
+
+use crate::{snap, RayCasting};
+
+/// Number of results kept by `QueryCache::new`
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Geometry wrapper memoizing the most recent `within` results
+///
+/// Query points are snapped to the nearest node of a square grid, `grid` wide, and the result for
+/// the node is computed once and reused for every point snapping to it, until it's evicted to make
+/// room for newer ones. Results are hence those of the snapped points, exact only for points
+/// lying on the grid, but depend neither on the order of the queries nor on the capacity: nodes
+/// too far to be stored, and every node when the capacity is 0, are queried without being kept.
+/// A non-positive `grid` disables snapping, and caching with it.
+///
+/// The cache is kept in a `RefCell`, so the wrapper can't be shared between threads.
+#[derive(Debug, Clone)]
+pub struct QueryCache<T: CoordFloat, G> {
+    geom: G,
+    grid: T,
+    capacity: usize,
+    results: RefCell<BTreeMap<(i64, i64), bool>>,
+    order: RefCell<VecDeque<(i64, i64)>>,
+}
+
+impl<T: CoordFloat, G: RayCasting<T, Coord<T>>> QueryCache<T, G> {
+    /// Wraps a geometry, snapping queries to a grid `grid` wide and keeping up to 1024 results
+    pub fn new(geom: G, grid: T) -> Self {
+        QueryCache::with_capacity(geom, grid, DEFAULT_CAPACITY)
+    }
+
+    /// Wraps a geometry, snapping queries to a grid `grid` wide and keeping up to `capacity`
+    /// results
+    ///
+    /// Storage grows along with the results, so `usize::MAX` keeps every result.
+    pub fn with_capacity(geom: G, grid: T, capacity: usize) -> Self {
+        QueryCache {
+            geom,
+            grid,
+            capacity,
+            results: RefCell::new(BTreeMap::new()),
+            order: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    /// Wrapped geometry
+    pub fn geometry(&self) -> &G {
+        &self.geom
+    }
+
+    /// Number of results currently kept
+    pub fn len(&self) -> usize {
+        self.results.borrow().len()
+    }
+
+    /// Checks if no result is kept
+    pub fn is_empty(&self) -> bool {
+        self.results.borrow().is_empty()
+    }
+
+    /// Forgets every result
+    pub fn clear(&self) {
+        self.results.borrow_mut().clear();
+        self.order.borrow_mut().clear();
+    }
+
+    fn query(&self, pt: &Coord<T>) -> bool {
+        let (snapped, x, y) = match snap(pt, self.grid) {
+            Some(node) => node,
+            None => return self.geom.within(pt),
+        };
+        let key = match (x.to_i64(), y.to_i64()) {
+            (Some(x), Some(y)) if self.capacity > 0 => (x, y),
+            _ => return self.geom.within(&snapped),
+        };
+        if let Some(&within) = self.results.borrow().get(&key) {
+            return within;
+        }
+
+        let within = self.geom.within(&snapped);
+        let (mut results, mut order) = (self.results.borrow_mut(), self.order.borrow_mut());
+        if order.len() == self.capacity {
+            if let Some(oldest) = order.pop_front() {
+                results.remove(&oldest);
+            }
+        }
+        results.insert(key, within);
+        order.push_back(key);
+        within
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy, G: RayCasting<T, Coord<T>>> RayCasting<T, P>
+    for QueryCache<T, G>
+{
+    fn within(&self, pt: &P) -> bool {
+        self.query(&(*pt).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryCache;
+    use crate::RayCasting;
+
+    use geo_types::{Coord, LineString, Point};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        (x, y).into()
+    }
+
+    fn square() -> LineString<f64> {
+        vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn cache_hit_matches_cold() {
+        let poly_square = square();
+        let cache = QueryCache::new(&poly_square, 0.5);
+        for pt in [p(5.0, 5.0), p(-10.0, 5.0), p(0.0, 5.0), p(10.0, 5.0)] {
+            let cold = cache.within(&pt);
+            assert_eq!(cold, poly_square.within(&pt));
+            let len = cache.len();
+            assert_eq!(cache.within(&pt), cold);
+            assert_eq!(cache.within(&Point(pt)), cold);
+            assert_eq!(cache.len(), len);
+        }
+        assert_eq!(cache.len(), 4);
+        // snapped to the same node as (10, 5), whatever the order of the queries
+        assert!(poly_square.within(&p(9.9, 5.1)));
+        assert!(!cache.within(&p(9.9, 5.1)));
+        assert_eq!(cache.len(), 4);
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(!cache.within(&p(9.9, 5.1)));
+
+        for x in -4..=44 {
+            for y in -4..=44 {
+                let pt = p(f64::from(x) * 0.3, f64::from(y) * 0.3);
+                assert_eq!(cache.within(&pt), poly_square.within_snapped(&pt, 0.5));
+            }
+        }
+    }
+
+    #[test]
+    fn cache_eviction() {
+        let poly_square = square();
+        let cache = QueryCache::with_capacity(poly_square.clone(), 1.0, 2);
+        assert!(cache.within(&p(1.0, 1.0)));
+        assert!(cache.within(&p(2.0, 2.0)));
+        assert!(!cache.within(&p(12.0, 2.0)));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.within(&p(1.0, 1.0)));
+        assert_eq!(cache.geometry(), &poly_square);
+
+        // results are still those of the snapped points without storage
+        let uncached = QueryCache::with_capacity(&poly_square, 1.0, 0);
+        assert!(poly_square.within(&p(9.9, 5.1)));
+        assert!(!uncached.within(&p(9.9, 5.1)));
+        assert!(uncached.within(&p(0.4, 5.1)));
+        assert!(uncached.is_empty());
+        assert!(!cache.within(&p(f64::NAN, 5.0)));
+        assert!(!cache.within(&p(1e30, 5.0)));
+
+        let unbounded = QueryCache::with_capacity(&poly_square, 1.0, usize::MAX);
+        assert!(unbounded.within(&p(5.0, 5.0)));
+        assert!(!unbounded.within(&p(15.0, 5.0)));
+        assert_eq!(unbounded.len(), 2);
+
+        let unsnapped = QueryCache::new(&poly_square, 0.0);
+        assert!(unsnapped.within(&p(9.9, 5.1)));
+        assert!(!unsnapped.within(&p(10.0, 5.1)));
+        assert!(unsnapped.is_empty());
+    }
+}
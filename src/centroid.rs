@@ -0,0 +1,242 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Centroid and interior representative points
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use geo_types::{Coord, CoordFloat, Line, LineString, Point, Polygon, Rect};
+
+use crate::{bounding_rect, ring_lines, Area, RayCasting};
+
+/// Trait computing points representative of a polygonal area
+pub trait Centroid<T: CoordFloat> {
+    /// Center of mass of the area
+    ///
+    /// Areas with no surface fall back to the mean of their vertices, empty ones to NaN.
+    fn centroid(&self) -> Point<T>;
+
+    /// Point guaranteed to be within the area, as reported by `RayCasting::within`
+    ///
+    /// This is the centroid when it's within the area, otherwise the midpoint of the widest
+    /// span along a horizontal scanline through the area. Areas with no surface return the
+    /// centroid anyway.
+    fn representative_point(&self) -> Point<T>;
+}
+
+/// Area weighted centroid of a ring, as `(signed area, centroid)`
+fn ring_centroid<T: CoordFloat>(ring: &LineString<T>) -> (T, Coord<T>) {
+    let six = T::from(6.0).unwrap();
+    let area = ring.signed_area();
+    let (x, y) = ring_lines(ring).fold((T::zero(), T::zero()), |(x, y), line| {
+        let det = line.determinant();
+        (
+            x + (line.start.x + line.end.x) * det,
+            y + (line.start.y + line.end.y) * det,
+        )
+    });
+    (
+        area,
+        Coord {
+            x: x / (six * area),
+            y: y / (six * area),
+        },
+    )
+}
+
+fn vertices_mean<'a, T: CoordFloat + 'a, I: IntoIterator<Item = &'a Coord<T>>>(
+    coords: I,
+) -> Point<T> {
+    let (sum, count) = coords
+        .into_iter()
+        .fold((Coord::zero(), T::zero()), |(sum, count), c| {
+            (sum + *c, count + T::one())
+        });
+    Point(sum / count)
+}
+
+/// Midpoint of the widest span inside `lines` along the horizontal scanline at `y`
+fn scanline_midpoint<T: CoordFloat, I: IntoIterator<Item = Line<T>>>(
+    lines: I,
+    y: T,
+) -> Option<Coord<T>> {
+    let mut xs = lines
+        .into_iter()
+        .filter(|line| (line.start.y <= y) != (line.end.y <= y))
+        .map(|line| line.start.x + (y - line.start.y) / line.dy() * line.dx())
+        .collect::<Vec<_>>();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    let mut widest: Option<&[T]> = None;
+    for span in xs.chunks_exact(2) {
+        if widest.map_or(true, |w| span[1] - span[0] > w[1] - w[0]) {
+            widest = Some(span);
+        }
+    }
+
+    let two = T::one() + T::one();
+    widest.map(|span| Coord {
+        x: (span[0] + span[1]) / two,
+        y,
+    })
+}
+
+/// Tries scanlines at a few heights, keeping the first midpoint within `geom`
+fn representative<T, G, F, I>(
+    geom: &G,
+    centroid: Point<T>,
+    rect: Option<Rect<T>>,
+    lines: F,
+) -> Point<T>
+where
+    T: CoordFloat,
+    G: RayCasting<T, Coord<T>>,
+    F: Fn() -> I,
+    I: Iterator<Item = Line<T>>,
+{
+    if geom.within(&centroid.0) {
+        return centroid;
+    }
+
+    let rect = match rect {
+        Some(rect) => rect,
+        None => return centroid,
+    };
+    let fractions = [0.5, 0.25, 0.75, 0.375, 0.625, 0.125, 0.875];
+    fractions
+        .iter()
+        .filter_map(|&f| T::from(f))
+        .map(|f| rect.min().y + rect.height() * f)
+        .chain(core::iter::once(centroid.y()))
+        .filter_map(|y| scanline_midpoint(lines(), y))
+        .find(|c| geom.within(c))
+        .map_or(centroid, Point)
+}
+
+impl<T: CoordFloat> Centroid<T> for LineString<T> {
+    fn centroid(&self) -> Point<T> {
+        let (area, centroid) = ring_centroid(self);
+        if area == T::zero() {
+            vertices_mean(self.coords())
+        } else {
+            Point(centroid)
+        }
+    }
+
+    fn representative_point(&self) -> Point<T> {
+        representative(self, self.centroid(), bounding_rect(self.coords()), || {
+            ring_lines(self)
+        })
+    }
+}
+
+impl<T: CoordFloat> Centroid<T> for Polygon<T> {
+    fn centroid(&self) -> Point<T> {
+        let (area, centroid) = self
+            .interiors()
+            .iter()
+            .map(|ring| {
+                let (area, centroid) = ring_centroid(ring);
+                (-area.abs(), centroid)
+            })
+            .chain(core::iter::once({
+                let (area, centroid) = ring_centroid(self.exterior());
+                (area.abs(), centroid)
+            }))
+            .filter(|(area, _)| *area != T::zero())
+            .fold(
+                (T::zero(), Coord::zero()),
+                |(total, sum), (area, centroid)| (total + area, sum + centroid * area),
+            );
+        if area == T::zero() {
+            self.exterior().centroid()
+        } else {
+            Point(centroid / area)
+        }
+    }
+
+    fn representative_point(&self) -> Point<T> {
+        representative(
+            self,
+            self.centroid(),
+            bounding_rect(self.exterior().coords()),
+            || ring_lines(self.exterior()).chain(self.interiors().iter().flat_map(ring_lines)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Centroid;
+    use crate::RayCasting;
+
+    use geo_types::{LineString, Point, Polygon};
+
+    fn square() -> LineString<f64> {
+        vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn centroid_square() {
+        let poly_square = square();
+        assert_eq!(poly_square.centroid(), Point::new(5.0, 5.0));
+        assert_eq!(poly_square.representative_point(), Point::new(5.0, 5.0));
+
+        let mut reversed = poly_square;
+        reversed.0.reverse();
+        assert_eq!(reversed.centroid(), Point::new(5.0, 5.0));
+
+        let flat: LineString<f64> = vec![(0.0, 0.0), (4.0, 0.0), (8.0, 0.0), (0.0, 0.0)].into();
+        assert_eq!(flat.centroid(), Point::new(3.0, 0.0));
+    }
+
+    #[test]
+    fn representative_point_square_hole() {
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        assert_eq!(poly_square_hole.centroid(), Point::new(5.0, 5.0));
+        assert!(!poly_square_hole.within(&poly_square_hole.centroid()));
+
+        let representative = poly_square_hole.representative_point();
+        assert!(poly_square_hole.within(&representative));
+        assert!(!poly_square_hole.interiors()[0].within(&representative));
+    }
+
+    #[test]
+    fn representative_point_concave() {
+        let poly_u: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (9.0, 10.0),
+            (9.0, 1.0),
+            (1.0, 1.0),
+            (1.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert!(!poly_u.within(&poly_u.centroid()));
+        assert!(poly_u.within(&poly_u.representative_point()));
+    }
+}
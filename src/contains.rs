@@ -0,0 +1,267 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Containment of whole geometries
+
+use geo_types::{Coord, CoordFloat, Line, LineString, Polygon};
+
+use crate::{cross, ring_lines, segments_intersect, Centroid, Locate, Location, RayCasting};
+
+/// Trait checking if whole geometries lie within a polygon
+pub trait Containment<T: CoordFloat> {
+    /// Checks if every vertex of `line` is within the polygon and none of its segments crosses
+    /// the polygon's boundary
+    ///
+    /// Testing vertices alone isn't enough, since on concave polygons a segment can leave the
+    /// polygon between two vertices within it. Touching the boundary counts as crossing it.
+    fn contains_linestring(&self, line: &LineString<T>) -> bool;
+
+    /// Checks if `other`'s exterior lies within the polygon without crossing its boundary, and
+    /// `other` doesn't cover any of the polygon's holes
+    fn contains_polygon(&self, other: &Polygon<T>) -> bool;
+
+    /// Checks if `seg` intersects any exterior or interior edge, regardless of where its ends lie
+    ///
+    /// Touching an edge, or just a vertex, counts as crossing it.
+    fn crosses_boundary(&self, seg: &Line<T>) -> bool;
+
+    /// Checks if the polygon and `other` share some interior area
+    ///
+    /// They do when their edges properly cross, or when a vertex, an edge midpoint or the
+    /// representative point of either lies strictly inside the other. Polygons only touching
+    /// along their boundaries don't overlap.
+    fn overlaps(&self, other: &Polygon<T>) -> bool;
+}
+
+/// Alias of `RayCasting` following the `geo` crate naming, implemented for every type
+/// implementing `RayCasting`
+///
+/// ```
+/// use geo_raycasting::Contains;
+/// use geo_types::{LineString, Point, Polygon};
+///
+/// let square: LineString<f64> = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+/// let polygon = Polygon::new(square, vec![]);
+///
+/// assert!(polygon.contains(&Point::new(5.0, 5.0)));
+/// assert!(!polygon.contains(&Point::new(15.0, 5.0)));
+/// ```
+pub trait Contains<T: CoordFloat, P: Into<Coord<T>>> {
+    /// Checks if a point is within a polygonal area, same as `RayCasting::within`
+    fn contains(&self, pt: &P) -> bool;
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>>, G: RayCasting<T, P> + ?Sized> Contains<T, P> for G {
+    fn contains(&self, pt: &P) -> bool {
+        self.within(pt)
+    }
+}
+
+/// Checks if two segments cross at a single point lying strictly inside both
+fn segments_cross<T: CoordFloat>(first: &Line<T>, second: &Line<T>) -> bool {
+    let zero = T::zero();
+    let opposite = |a: T, b: T| (a > zero && b < zero) || (a < zero && b > zero);
+    opposite(
+        cross(&first.start, &first.end, &second.start),
+        cross(&first.start, &first.end, &second.end),
+    ) && opposite(
+        cross(&second.start, &second.end, &first.start),
+        cross(&second.start, &second.end, &first.end),
+    )
+}
+
+fn polygon_lines<T: CoordFloat>(poly: &Polygon<T>) -> impl Iterator<Item = Line<T>> + '_ {
+    ring_lines(poly.exterior()).chain(poly.interiors().iter().flat_map(ring_lines))
+}
+
+/// Checks if any vertex, edge midpoint or the representative point of `inner` lies strictly
+/// inside `outer`
+fn reaches_inside<T: CoordFloat>(outer: &Polygon<T>, inner: &Polygon<T>) -> bool {
+    let inside = |pt: &Coord<T>| outer.locate_eps(pt, T::zero()) == Location::Inside;
+    let two = T::one() + T::one();
+    polygon_lines(inner).any(|line| inside(&line.start) || inside(&((line.start + line.end) / two)))
+        || (!inner.exterior().0.is_empty() && inside(&inner.representative_point().0))
+}
+
+impl<T: CoordFloat> Containment<T> for Polygon<T> {
+    fn contains_linestring(&self, line: &LineString<T>) -> bool {
+        !line.0.is_empty()
+            && line.coords().all(|c| self.within(c))
+            && !line.lines().any(|seg| self.crosses_boundary(&seg))
+    }
+
+    fn contains_polygon(&self, other: &Polygon<T>) -> bool {
+        // holes don't cross `other`'s exterior here, so they're either fully covered or not at all
+        self.contains_linestring(other.exterior())
+            && !self
+                .interiors()
+                .iter()
+                .any(|hole| hole.0.first().is_some_and(|c| other.within(c)))
+    }
+
+    fn crosses_boundary(&self, seg: &Line<T>) -> bool {
+        ring_lines(self.exterior())
+            .chain(self.interiors().iter().flat_map(ring_lines))
+            .any(|edge| segments_intersect(seg, &edge))
+    }
+
+    fn overlaps(&self, other: &Polygon<T>) -> bool {
+        polygon_lines(self)
+            .any(|line| polygon_lines(other).any(|edge| segments_cross(&line, &edge)))
+            || reaches_inside(self, other)
+            || reaches_inside(other, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Containment;
+
+    use geo_types::{Line, LineString, Polygon};
+
+    fn square(min: f64, max: f64) -> LineString<f64> {
+        LineString::from(vec![
+            (min, min),
+            (max, min),
+            (max, max),
+            (min, max),
+            (min, min),
+        ])
+    }
+
+    fn poly_u() -> Polygon<f64> {
+        Polygon::new(
+            LineString::from(vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (7.0, 10.0),
+                (7.0, 3.0),
+                (3.0, 3.0),
+                (3.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ]),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn contains_linestring_concave() {
+        let poly_u = poly_u();
+        assert!(poly_u.contains_linestring(&LineString::from(vec![
+            (1.0, 8.0),
+            (1.0, 1.0),
+            (9.0, 1.0),
+            (9.0, 8.0),
+        ])));
+        // both vertices are within the arms of the U, but the path dips out through the notch
+        assert!(!poly_u.contains_linestring(&LineString::from(vec![(1.0, 8.0), (9.0, 8.0)])));
+        assert!(!poly_u.contains_linestring(&LineString::from(vec![(1.0, 8.0), (5.0, 8.0)])));
+        assert!(!poly_u.contains_linestring(&LineString(vec![])));
+    }
+
+    #[test]
+    fn contains_linestring_hole() {
+        let poly_square_hole = Polygon::new(
+            LineString::from(vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ]),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        assert!(
+            poly_square_hole.contains_linestring(&LineString::from(vec![(1.0, 1.0), (9.0, 1.0)]))
+        );
+        assert!(
+            !poly_square_hole.contains_linestring(&LineString::from(vec![(1.0, 5.0), (9.0, 5.0)]))
+        );
+    }
+
+    #[test]
+    fn crosses_boundary_square() {
+        let poly_square = Polygon::new(square(0.0, 10.0), vec![]);
+        assert!(poly_square.crosses_boundary(&Line::from([(-5.0, 5.0), (15.0, 5.0)])));
+        assert!(poly_square.crosses_boundary(&Line::from([(5.0, 5.0), (15.0, 5.0)])));
+        assert!(poly_square.crosses_boundary(&Line::from([(5.0, 5.0), (10.0, 10.0)])));
+        assert!(!poly_square.crosses_boundary(&Line::from([(2.0, 2.0), (8.0, 8.0)])));
+        assert!(!poly_square.crosses_boundary(&Line::from([(12.0, 2.0), (18.0, 8.0)])));
+
+        let poly_square_hole = Polygon::new(square(0.0, 10.0), vec![square(2.5, 7.5)]);
+        assert!(poly_square_hole.crosses_boundary(&Line::from([(1.0, 5.0), (5.0, 5.0)])));
+        assert!(!poly_square_hole.crosses_boundary(&Line::from([(1.0, 1.0), (9.0, 1.0)])));
+    }
+
+    #[test]
+    fn contains_polygon_square() {
+        let poly_square = Polygon::new(square(0.0, 10.0), vec![]);
+        assert!(poly_square.contains_polygon(&Polygon::new(square(2.0, 4.0), vec![])));
+        assert!(!poly_square.contains_polygon(&Polygon::new(square(8.0, 12.0), vec![])));
+        assert!(!poly_square.contains_polygon(&Polygon::new(square(12.0, 14.0), vec![])));
+        assert!(!poly_square.contains_polygon(&Polygon::new(square(-1.0, 11.0), vec![])));
+    }
+
+    #[test]
+    fn contains_polygon_hole() {
+        let poly_square_hole = Polygon::new(square(0.0, 10.0), vec![square(2.5, 7.5)]);
+        assert!(poly_square_hole.contains_polygon(&Polygon::new(square(0.5, 2.0), vec![])));
+        assert!(!poly_square_hole.contains_polygon(&Polygon::new(square(2.0, 4.0), vec![])));
+        assert!(!poly_square_hole.contains_polygon(&Polygon::new(square(1.0, 9.0), vec![])));
+        assert!(!poly_square_hole.contains_polygon(&Polygon::new(square(4.0, 6.0), vec![])));
+        assert!(poly_square_hole
+            .contains_polygon(&Polygon::new(square(1.0, 9.0), vec![square(2.0, 8.0)])));
+    }
+
+    #[test]
+    fn overlaps_squares() {
+        let poly_square = Polygon::new(square(0.0, 10.0), vec![]);
+        let overlapping = Polygon::new(square(5.0, 15.0), vec![]);
+        assert!(poly_square.overlaps(&overlapping));
+        assert!(overlapping.overlaps(&poly_square));
+        let disjoint = Polygon::new(square(20.0, 30.0), vec![]);
+        assert!(!poly_square.overlaps(&disjoint));
+        assert!(!disjoint.overlaps(&poly_square));
+
+        // nested or identical, no edge crosses
+        assert!(poly_square.overlaps(&Polygon::new(square(2.0, 4.0), vec![])));
+        assert!(Polygon::new(square(2.0, 4.0), vec![]).overlaps(&poly_square));
+        assert!(poly_square.overlaps(&poly_square.clone()));
+
+        // a cross shape, with no vertex inside the other
+        let wide = Polygon::new(
+            LineString::from(vec![(-5.0, 4.0), (15.0, 4.0), (15.0, 6.0), (-5.0, 6.0)]),
+            vec![],
+        );
+        let tall = Polygon::new(
+            LineString::from(vec![(4.0, -5.0), (6.0, -5.0), (6.0, 15.0), (4.0, 15.0)]),
+            vec![],
+        );
+        assert!(wide.overlaps(&tall));
+
+        // touching along an edge or at a corner isn't overlapping
+        let adjacent = Polygon::new(
+            LineString::from(vec![(10.0, 0.0), (20.0, 0.0), (20.0, 10.0), (10.0, 10.0)]),
+            vec![],
+        );
+        assert!(!poly_square.overlaps(&adjacent));
+        assert!(!adjacent.overlaps(&poly_square));
+        assert!(!poly_square.overlaps(&Polygon::new(square(10.0, 20.0), vec![])));
+
+        let poly_square_hole = Polygon::new(square(0.0, 10.0), vec![square(2.5, 7.5)]);
+        assert!(!poly_square_hole.overlaps(&Polygon::new(square(4.0, 6.0), vec![])));
+        assert!(poly_square_hole.overlaps(&Polygon::new(square(1.0, 3.0), vec![])));
+    }
+}
@@ -0,0 +1,350 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Convexity detection and containment fast path
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use geo_types::{Coord, CoordFloat, Line, LineString, Polygon};
+
+use crate::{cross, pt_in_polygon, ring_lines};
+
+/// Trait detecting convex rings
+pub trait Convexity<T: CoordFloat> {
+    /// Checks if the (exterior) ring is convex, turning the same direction at every vertex and
+    /// winding only once
+    ///
+    /// Collinear vertices are allowed, degenerate rings and polygons with holes aren't convex.
+    fn is_convex(&self) -> bool;
+}
+
+/// Containment test for convex polygons
+pub trait ConvexRayCasting<T: CoordFloat, P: Into<Coord<T>>> {
+    /// Checks if a point is within a convex polygonal area, by checking it lies on the same side
+    /// of every segment
+    ///
+    /// Cheaper and more robust than `RayCasting::within`, but the result is undefined when the
+    /// ring isn't convex, see `Convexity::is_convex`. Boundary points are outside.
+    fn within_convex(&self, pt: &P) -> bool;
+
+    /// Side of every segment a point lies on, from the sign of the cross product: `Greater` on
+    /// the left, `Less` on the right and `Equal` on the segment's line
+    ///
+    /// Segments are listed in ring order, as yielded by `Ring::closed_lines`, zero-length ones
+    /// included, with the exterior's first and then each hole's. A point is within a convex ring
+    /// when every side is `Greater`, or every side is `Less`, ignoring zero-length segments.
+    fn edge_sides(&self, pt: &P) -> Vec<Ordering>;
+}
+
+/// Convex hull of a point set, with Andrew's monotone chain algorithm
+///
+/// The hull is returned as a closed ring wound counter-clockwise, starting from the lowest
+/// leftmost point, ready to be queried with `RayCasting::within`. Points lying along the hull's
+/// edges are dropped, only its corners are kept. With fewer than 3 non-collinear points the ring
+/// is degenerate, and contains no point.
+pub fn convex_hull<T: CoordFloat>(points: &[Coord<T>]) -> LineString<T> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap_or(Ordering::Equal)
+            .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+    });
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return LineString(sorted);
+    }
+
+    let half = |points: &mut dyn Iterator<Item = &Coord<T>>| {
+        let mut chain: Vec<Coord<T>> = Vec::new();
+        for pt in points {
+            while chain.len() >= 2
+                && cross(&chain[chain.len() - 2], &chain[chain.len() - 1], pt) <= T::zero()
+            {
+                chain.pop();
+            }
+            chain.push(*pt);
+        }
+        // the last point starts the other half
+        chain.pop();
+        chain
+    };
+    let mut hull = half(&mut sorted.iter());
+    hull.extend(half(&mut sorted.iter().rev()));
+    let mut hull = LineString(hull);
+    hull.close();
+    hull
+}
+
+fn ring_is_convex<T: CoordFloat>(ring: &LineString<T>) -> bool {
+    let mut vertices: Vec<Coord<T>> = Vec::with_capacity(ring.0.len());
+    for coord in ring.coords() {
+        if vertices.last() != Some(coord) {
+            vertices.push(*coord);
+        }
+    }
+    if vertices.len() > 1 && vertices.first() == vertices.last() {
+        vertices.pop();
+    }
+    if vertices.len() < 3 {
+        return false;
+    }
+
+    let mut sign = T::zero();
+    let mut turning = T::zero();
+    for i in 0..vertices.len() {
+        let previous = vertices[(i + vertices.len() - 1) % vertices.len()];
+        let current = vertices[i];
+        let next = vertices[(i + 1) % vertices.len()];
+        let turn = cross(&previous, &current, &next);
+        let (first, second) = (current - previous, next - current);
+        let dot = first.x * second.x + first.y * second.y;
+        if turn == T::zero() {
+            // going straight is fine, going back isn't
+            if dot < T::zero() {
+                return false;
+            }
+            continue;
+        }
+        if sign * turn < T::zero() {
+            return false;
+        }
+        sign = turn.signum();
+        turning = turning + turn.atan2(dot);
+    }
+    // a star shaped ring turns the same direction everywhere, but winds more than once
+    let two_pi = T::from(2.0 * core::f64::consts::PI).unwrap();
+    sign != T::zero() && turning.abs() < two_pi + T::from(1e-3).unwrap()
+}
+
+fn pt_in_convex_ring<T: CoordFloat>(pt: &Coord<T>, ring: &LineString<T>) -> bool {
+    let mut sign = T::zero();
+    for line in ring_lines(ring).filter(|line| line.start != line.end) {
+        let side = cross(&line.start, &line.end, pt);
+        if side == T::zero() || sign * side < T::zero() {
+            return false;
+        }
+        sign = side.signum();
+    }
+    sign != T::zero()
+}
+
+fn sides<T: CoordFloat, I: IntoIterator<Item = Line<T>>>(pt: &Coord<T>, lines: I) -> Vec<Ordering> {
+    lines
+        .into_iter()
+        .map(|line| {
+            cross(&line.start, &line.end, pt)
+                .partial_cmp(&T::zero())
+                .unwrap_or(Ordering::Equal)
+        })
+        .collect()
+}
+
+impl<T: CoordFloat> Convexity<T> for LineString<T> {
+    fn is_convex(&self) -> bool {
+        ring_is_convex(self)
+    }
+}
+
+/// `geo_types::Polygon` has a deprecated inherent `is_convex` method shadowing this one, call it
+/// as `Convexity::is_convex(&poly)`.
+impl<T: CoordFloat> Convexity<T> for Polygon<T> {
+    fn is_convex(&self) -> bool {
+        self.interiors().is_empty() && ring_is_convex(self.exterior())
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> ConvexRayCasting<T, P> for LineString<T> {
+    fn within_convex(&self, pt: &P) -> bool {
+        pt_in_convex_ring(&(*pt).into(), self)
+    }
+
+    fn edge_sides(&self, pt: &P) -> Vec<Ordering> {
+        sides(&(*pt).into(), ring_lines(self))
+    }
+}
+
+/// Only the exterior needs to be convex, holes are checked with the plain ray cast.
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> ConvexRayCasting<T, P> for Polygon<T> {
+    fn within_convex(&self, pt: &P) -> bool {
+        let coord = (*pt).into();
+        pt_in_convex_ring(&coord, self.exterior())
+            && !self
+                .interiors()
+                .iter()
+                .any(|ring| pt_in_polygon(&coord, ring))
+    }
+
+    fn edge_sides(&self, pt: &P) -> Vec<Ordering> {
+        sides(
+            &(*pt).into(),
+            ring_lines(self.exterior()).chain(self.interiors().iter().flat_map(ring_lines)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convex_hull, ConvexRayCasting, Convexity};
+    use crate::RayCasting;
+
+    use core::cmp::Ordering;
+
+    use geo_types::{Coord, LineString, Point, Polygon};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        Coord { x, y }
+    }
+
+    fn hexagon() -> LineString<f64> {
+        vec![
+            (3.0, 0.0),
+            (7.0, 0.0),
+            (10.0, 5.0),
+            (7.0, 10.0),
+            (3.0, 10.0),
+            (0.0, 5.0),
+            (3.0, 0.0),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn convex_hexagon() {
+        let poly_hexagon = hexagon();
+        assert!(poly_hexagon.is_convex());
+        assert!(poly_hexagon.within_convex(&p(5.0, 5.0)));
+        assert!(poly_hexagon.within_convex(&Point::new(5.0, 8.0)));
+        assert!(poly_hexagon.within_convex(&p(8.0, 5.0)));
+        assert!(!poly_hexagon.within_convex(&p(-10.0, 5.0)));
+        assert!(!poly_hexagon.within_convex(&p(0.0, 5.0)));
+        assert!(!poly_hexagon.within_convex(&p(10.0, 5.0)));
+        assert!(!poly_hexagon.within_convex(&p(10.0, 10.0)));
+
+        let mut reversed = poly_hexagon.clone();
+        reversed.0.reverse();
+        assert!(reversed.is_convex());
+        for x in 0..20 {
+            for y in 0..20 {
+                let pt = p(f64::from(x) * 0.5 + 0.1, f64::from(y) * 0.5 + 0.1);
+                assert_eq!(reversed.within_convex(&pt), poly_hexagon.within(&pt));
+            }
+        }
+    }
+
+    #[test]
+    fn edge_sides_hexagon() {
+        let poly_hexagon = hexagon();
+        let mut reversed = poly_hexagon.clone();
+        reversed.0.reverse();
+        for x in 0..20 {
+            for y in 0..20 {
+                let pt = p(f64::from(x) * 0.5 + 0.1, f64::from(y) * 0.5 + 0.1);
+                let sides = poly_hexagon.edge_sides(&pt);
+                assert_eq!(sides.len(), 6);
+                if poly_hexagon.within(&pt) {
+                    assert!(
+                        sides.iter().all(|side| *side == Ordering::Greater),
+                        "{:?}",
+                        pt
+                    );
+                    let reversed_sides = reversed.edge_sides(&pt);
+                    assert!(reversed_sides.iter().all(|side| *side == Ordering::Less));
+                } else {
+                    assert!(sides.contains(&Ordering::Less), "{:?}", pt);
+                }
+            }
+        }
+
+        let sides = poly_hexagon.edge_sides(&p(5.0, 0.0));
+        assert_eq!(sides[0], Ordering::Equal);
+        assert!(sides[1..].iter().all(|side| *side == Ordering::Greater));
+
+        let poly = Polygon::new(
+            poly_hexagon,
+            vec![vec![(4.0, 4.0), (6.0, 4.0), (6.0, 6.0), (4.0, 6.0)].into()],
+        );
+        let sides = poly.edge_sides(&Point::new(5.0, 5.0));
+        assert_eq!(sides.len(), 10);
+        assert!(sides.iter().all(|side| *side == Ordering::Greater));
+    }
+
+    #[test]
+    fn not_convex() {
+        let poly_strange: LineString<f64> = vec![
+            (0.0, 0.0),
+            (2.5, 2.5),
+            (0.0, 10.0),
+            (2.5, 7.5),
+            (7.5, 7.5),
+            (10.0, 10.0),
+            (10.0, 0.0),
+            (2.5, 2.5),
+        ]
+        .into();
+        assert!(!poly_strange.is_convex());
+
+        let pentagram: LineString<f64> = (0..5)
+            .map(|i| {
+                let angle = f64::from(i * 2) * 2.0 * std::f64::consts::PI / 5.0;
+                (angle.cos(), angle.sin())
+            })
+            .collect::<Vec<_>>()
+            .into();
+        assert!(!pentagram.is_convex());
+
+        let degenerate: LineString<f64> = vec![(0.0, 0.0), (5.0, 0.0), (10.0, 0.0)].into();
+        assert!(!degenerate.is_convex());
+
+        let square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (5.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+        ]
+        .into();
+        assert!(square.is_convex());
+        let hole: LineString<f64> = vec![(2.5, 2.5), (7.5, 2.5), (7.5, 7.5), (2.5, 7.5)].into();
+        let poly_square_hole = Polygon::new(square, vec![hole]);
+        assert!(!Convexity::is_convex(&poly_square_hole));
+        assert!(poly_square_hole.within_convex(&p(5.0, 8.0)));
+        assert!(!poly_square_hole.within_convex(&p(5.0, 5.0)));
+    }
+
+    #[test]
+    fn convex_hull_square() {
+        let points = vec![
+            p(5.0, 5.0),
+            p(10.0, 10.0),
+            p(2.0, 7.0),
+            p(0.0, 10.0),
+            p(5.0, 0.0),
+            p(10.0, 0.0),
+            p(0.0, 0.0),
+            p(8.0, 1.0),
+            p(0.0, 0.0),
+        ];
+        let hull = convex_hull(&points);
+        let square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert_eq!(hull, square);
+        assert!(hull.within(&p(5.0, 5.0)));
+        assert!(!hull.within(&p(15.0, 5.0)));
+        assert!(hull.is_convex());
+
+        assert_eq!(convex_hull::<f64>(&[]), LineString(vec![]));
+        let line = convex_hull(&[p(0.0, 0.0), p(5.0, 5.0), p(10.0, 10.0)]);
+        assert!(!line.within(&p(5.0, 5.0)));
+    }
+}
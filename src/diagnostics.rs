@@ -0,0 +1,62 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Insights on how the ray cast classified a point
+
+use geo_types::{Coord, CoordFloat, LineString};
+
+use crate::{count_crossings, ring_lines};
+
+/// Trait exposing the internals of the ray cast, for debugging purposes
+pub trait RayDiagnostics<T: CoordFloat, P: Into<Coord<T>>> {
+    /// Number of segments crossed by the +X ray cast from a point
+    ///
+    /// An odd count means the point is within the ring, an even one that it's outside.
+    fn crossing_count(&self, pt: &P) -> usize;
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> RayDiagnostics<T, P> for LineString<T> {
+    fn crossing_count(&self, pt: &P) -> usize {
+        count_crossings(&(*pt).into(), ring_lines(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RayDiagnostics;
+    use crate::RayCasting;
+
+    use geo_types::{Coord, LineString};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        (x, y).into()
+    }
+
+    #[test]
+    fn crossing_count_square() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert_eq!(poly_square.crossing_count(&p(5.0, 5.0)), 1);
+        assert_eq!(poly_square.crossing_count(&p(-10.0, 5.0)), 2);
+        assert_eq!(poly_square.crossing_count(&p(15.0, 5.0)), 0);
+        // the ray runs along the top edge, above the half-open range of every segment
+        assert_eq!(poly_square.crossing_count(&p(-10.0, 10.0)), 0);
+        assert_eq!(poly_square.crossing_count(&p(-10.0, 0.0)), 2);
+        for pt in [p(5.0, 5.0), p(-10.0, 5.0), p(-10.0, 10.0), p(10.0, 10.0)] {
+            assert_eq!(
+                poly_square.crossing_count(&pt) % 2 == 1,
+                poly_square.within(&pt)
+            );
+        }
+    }
+}
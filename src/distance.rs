@@ -0,0 +1,449 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Distances between a point and a polygonal area's boundary
+
+use alloc::vec::Vec;
+
+use geo_types::{Coord, CoordFloat, Line, LineString, Polygon};
+
+use crate::{closest_point_on_segment, ring_lines, Centroid, RayCasting};
+
+/// Trait measuring how far a point lies from the boundary of a polygonal area
+pub trait BoundaryDistance<T: CoordFloat, P: Into<Coord<T>>> {
+    /// Distance from a point to the nearest boundary segment, negative when the point is within
+    /// the area as reported by `RayCasting::within`
+    fn signed_distance(&self, pt: &P) -> T;
+
+    /// Signed distances of a batch of points, the result is aligned index-for-index with `pts`
+    fn signed_distance_many(&self, pts: &[P]) -> Vec<T> {
+        pts.iter().map(|pt| self.signed_distance(pt)).collect()
+    }
+
+    /// Closest point to `pt` lying on the boundary
+    ///
+    /// An empty area has no boundary, in which case `pt` is returned unchanged.
+    fn nearest_boundary_point(&self, pt: &P) -> Coord<T>;
+
+    /// Point within the area as reported by `RayCasting::within`, `pt` itself when it already is
+    ///
+    /// Otherwise the nearest boundary point is nudged inwards, along the direction from `pt` to
+    /// it, or towards the representative point, or around it, when the first direction leaves the
+    /// area as happens near sharp vertices. The nudge starts at a few ulps and grows until the
+    /// point lands inside, the representative point itself is used as a last resort. Areas with
+    /// no interior return `pt` unchanged.
+    fn clamp_inside(&self, pt: &P) -> Coord<T>;
+
+    /// Checks if a point is within the area, or within `margin` of its boundary
+    ///
+    /// This behaves as `RayCasting::within` against the area dilated by `margin`, without
+    /// building the buffered polygon.
+    fn within_buffered(&self, pt: &P, margin: T) -> bool {
+        self.signed_distance(pt) <= margin
+    }
+
+    /// Checks if the disk centered at `center` overlaps the area, either because its center is
+    /// within it or because the boundary passes within `radius` of the center
+    ///
+    /// This is `within_buffered` seen from the disk's side, touching counts as overlapping.
+    fn intersects_circle(&self, center: &P, radius: T) -> bool {
+        self.within_buffered(center, radius)
+    }
+
+    /// Checks if a point is within the area, at least `margin` away from its boundary
+    ///
+    /// This behaves as `RayCasting::within` against the area eroded by `margin`.
+    fn deep_within(&self, pt: &P, margin: T) -> bool {
+        // points within the area get a negative distance, even when it's zero
+        let distance = self.signed_distance(pt);
+        distance.is_sign_negative() && -distance >= margin
+    }
+}
+
+/// Closest projection of `pt` on `lines`, along with its distance
+fn nearest<T: CoordFloat, I: IntoIterator<Item = Line<T>>>(
+    pt: &Coord<T>,
+    lines: I,
+) -> Option<(Coord<T>, T)> {
+    lines
+        .into_iter()
+        .map(|line| {
+            let closest = closest_point_on_segment(pt, &line);
+            (closest, (pt.x - closest.x).hypot(pt.y - closest.y))
+        })
+        .fold(None, |best, candidate| match best {
+            Some((_, distance)) if distance <= candidate.1 => best,
+            _ => Some(candidate),
+        })
+}
+
+fn polygon_nearest<T: CoordFloat>(pt: &Coord<T>, poly: &Polygon<T>) -> Option<(Coord<T>, T)> {
+    nearest(
+        pt,
+        ring_lines(poly.exterior()).chain(poly.interiors().iter().flat_map(ring_lines)),
+    )
+}
+
+/// Moves `nearest`, the boundary point closest to `pt`, within `geom`
+fn clamp<T: CoordFloat, G: RayCasting<T, Coord<T>> + Centroid<T>>(
+    geom: &G,
+    pt: Coord<T>,
+    nearest: Option<Coord<T>>,
+) -> Coord<T> {
+    if geom.within(&pt) {
+        return pt;
+    }
+    let boundary = match nearest {
+        Some(nearest) => nearest,
+        None => return pt,
+    };
+
+    let unit = |delta: Coord<T>| {
+        let norm = delta.x.hypot(delta.y);
+        if norm > T::zero() {
+            delta / norm
+        } else {
+            Coord {
+                x: T::one(),
+                y: T::zero(),
+            }
+        }
+    };
+    let inward = unit(boundary - pt);
+    let representative = geom.representative_point().0;
+    // the direction from `pt` first, then towards the representative point, then all around
+    let angle = T::from(core::f64::consts::PI / 8.0).unwrap();
+    let mut directions = Vec::with_capacity(17);
+    directions.push(inward);
+    directions.push(unit(representative - boundary));
+    directions.extend((1..16).map(|turn| {
+        let (sin, cos) = (angle * T::from(turn).unwrap()).sin_cos();
+        Coord {
+            x: inward.x * cos - inward.y * sin,
+            y: inward.x * sin + inward.y * cos,
+        }
+    }));
+
+    let scale = boundary.x.abs().max(boundary.y.abs()).max(T::one());
+    let mut step = scale * T::epsilon() * T::from(16.0).unwrap();
+    for _ in 0..32 {
+        for direction in &directions {
+            let candidate = boundary + *direction * step;
+            if geom.within(&candidate) {
+                return candidate;
+            }
+        }
+        step = step + step;
+    }
+
+    if geom.within(&representative) {
+        representative
+    } else {
+        pt
+    }
+}
+
+/// Signed distances of `pts` from `lines`, collected once for the whole batch
+fn distance_field<T, G, P>(geom: &G, lines: Vec<Line<T>>, pts: &[P]) -> Vec<T>
+where
+    T: CoordFloat,
+    G: RayCasting<T, Coord<T>>,
+    P: Into<Coord<T>> + Copy,
+{
+    pts.iter()
+        .map(|pt| {
+            let coord = (*pt).into();
+            let distance = nearest(&coord, lines.iter().copied()).map_or(T::infinity(), |(_, d)| d);
+            signed(distance, geom.within(&coord))
+        })
+        .collect()
+}
+
+fn signed<T: CoordFloat>(distance: T, within: bool) -> T {
+    if within {
+        -distance
+    } else {
+        distance
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> BoundaryDistance<T, P> for LineString<T> {
+    fn signed_distance(&self, pt: &P) -> T {
+        let coord = (*pt).into();
+        let distance = nearest(&coord, ring_lines(self)).map_or(T::infinity(), |(_, d)| d);
+        signed(distance, self.within(&coord))
+    }
+
+    fn signed_distance_many(&self, pts: &[P]) -> Vec<T> {
+        distance_field(self, ring_lines(self).collect(), pts)
+    }
+
+    fn nearest_boundary_point(&self, pt: &P) -> Coord<T> {
+        let coord = (*pt).into();
+        nearest(&coord, ring_lines(self)).map_or(coord, |(c, _)| c)
+    }
+
+    fn clamp_inside(&self, pt: &P) -> Coord<T> {
+        let coord = (*pt).into();
+        clamp(
+            self,
+            coord,
+            nearest(&coord, ring_lines(self)).map(|(c, _)| c),
+        )
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> BoundaryDistance<T, P> for Polygon<T> {
+    fn signed_distance(&self, pt: &P) -> T {
+        let coord = (*pt).into();
+        let distance = polygon_nearest(&coord, self).map_or(T::infinity(), |(_, d)| d);
+        signed(distance, self.within(&coord))
+    }
+
+    fn signed_distance_many(&self, pts: &[P]) -> Vec<T> {
+        let lines = ring_lines(self.exterior())
+            .chain(self.interiors().iter().flat_map(ring_lines))
+            .collect();
+        distance_field(self, lines, pts)
+    }
+
+    fn nearest_boundary_point(&self, pt: &P) -> Coord<T> {
+        let coord = (*pt).into();
+        polygon_nearest(&coord, self).map_or(coord, |(c, _)| c)
+    }
+
+    fn clamp_inside(&self, pt: &P) -> Coord<T> {
+        let coord = (*pt).into();
+        clamp(self, coord, polygon_nearest(&coord, self).map(|(c, _)| c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoundaryDistance;
+    use crate::RayCasting;
+
+    use geo_types::{Coord, LineString, Point, Polygon};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        (x, y).into()
+    }
+
+    fn square() -> LineString<f64> {
+        vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn signed_distance_square() {
+        let poly_square = square();
+        assert!((poly_square.signed_distance(&p(5.0, 5.0)) + 5.0).abs() < 1e-9);
+        assert!((poly_square.signed_distance(&Point::new(2.0, 5.0)) + 2.0).abs() < 1e-9);
+        assert!((poly_square.signed_distance(&p(15.0, 5.0)) - 5.0).abs() < 1e-9);
+        assert!((poly_square.signed_distance(&p(13.0, 14.0)) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn signed_distance_square_hole() {
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        assert!((poly_square_hole.signed_distance(&p(5.0, 5.0)) - 2.5).abs() < 1e-9);
+        assert!((poly_square_hole.signed_distance(&p(5.0, 9.0)) + 1.0).abs() < 1e-9);
+        assert!((poly_square_hole.signed_distance(&p(-1.0, 5.0)) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn signed_distance_many_square_hole() {
+        let poly_square = square();
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        let pts = [
+            p(5.0, 5.0),
+            p(5.0, 9.0),
+            p(-1.0, 5.0),
+            p(13.0, 14.0),
+            p(0.0, 5.0),
+        ];
+        assert_eq!(
+            poly_square.signed_distance_many(&pts),
+            pts.iter()
+                .map(|pt| poly_square.signed_distance(pt))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            poly_square_hole.signed_distance_many(&pts),
+            pts.iter()
+                .map(|pt| poly_square_hole.signed_distance(pt))
+                .collect::<Vec<_>>()
+        );
+        assert!(poly_square
+            .signed_distance_many(&[] as &[Coord<f64>])
+            .is_empty());
+    }
+
+    #[test]
+    fn within_buffered_square() {
+        let poly_square = square();
+        assert!(poly_square.within_buffered(&p(11.0, 5.0), 2.0));
+        assert!(poly_square.within_buffered(&p(5.0, 5.0), 2.0));
+        assert!(poly_square.within_buffered(&p(10.0, 5.0), 0.0));
+        assert!(!poly_square.within_buffered(&p(13.0, 5.0), 2.0));
+        assert!(!poly_square.within_buffered(&p(11.0, 5.0), 0.5));
+        // the margin rounds the corners
+        assert!(!poly_square.within_buffered(&p(11.5, 11.5), 2.0));
+        assert!(poly_square.within_buffered(&p(11.0, 11.0), 2.0));
+    }
+
+    #[test]
+    fn intersects_circle_square() {
+        let poly_square = square();
+        assert!(poly_square.intersects_circle(&p(12.0, 5.0), 3.0));
+        assert!(poly_square.intersects_circle(&p(12.0, 5.0), 2.0));
+        assert!(!poly_square.intersects_circle(&p(12.0, 5.0), 1.5));
+        assert!(poly_square.intersects_circle(&p(5.0, 5.0), 0.0));
+        assert!(!poly_square.intersects_circle(&p(13.0, 14.0), 4.9));
+        assert!(poly_square.intersects_circle(&Point::new(13.0, 14.0), 5.0));
+
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+            ])],
+        );
+        assert!(!poly_square_hole.intersects_circle(&p(5.0, 5.0), 2.0));
+        assert!(poly_square_hole.intersects_circle(&p(5.0, 5.0), 2.5));
+    }
+
+    #[test]
+    fn deep_within_square() {
+        let poly_square = square();
+        assert!(poly_square.deep_within(&p(5.0, 5.0), 4.0));
+        assert!(poly_square.deep_within(&p(5.0, 5.0), 5.0));
+        assert!(!poly_square.deep_within(&p(5.0, 5.0), 6.0));
+        assert!(!poly_square.deep_within(&p(9.0, 5.0), 2.0));
+        assert!(poly_square.deep_within(&p(0.0, 5.0), 0.0));
+        assert!(!poly_square.deep_within(&p(10.0, 5.0), 0.0));
+        assert!(!poly_square.deep_within(&p(15.0, 5.0), -10.0));
+    }
+
+    #[test]
+    fn clamp_inside_square() {
+        let poly_square = square();
+        let clamped = poly_square.clamp_inside(&p(15.0, 5.0));
+        assert!(poly_square.within(&clamped));
+        assert!((clamped.x - 10.0).abs() < 1e-9 && clamped.y == 5.0);
+
+        let clamped = poly_square.clamp_inside(&p(15.0, 15.0));
+        assert!(poly_square.within(&clamped));
+        assert!((clamped.x - 10.0).abs() < 1e-9 && (clamped.y - 10.0).abs() < 1e-9);
+
+        for pt in [p(-3.0, 5.0), p(5.0, -3.0), p(10.0, 5.0), p(0.0, 10.0)] {
+            let clamped = poly_square.clamp_inside(&pt);
+            assert!(poly_square.within(&clamped), "{:?}", pt);
+            assert!(poly_square.signed_distance(&clamped).abs() < 1e-9);
+        }
+        assert_eq!(poly_square.clamp_inside(&p(5.0, 5.0)), p(5.0, 5.0));
+        assert_eq!(
+            LineString::<f64>(vec![]).clamp_inside(&p(5.0, 5.0)),
+            p(5.0, 5.0)
+        );
+    }
+
+    #[test]
+    fn clamp_inside_spike() {
+        // a thin spike pointing right, approached from above its tip
+        let poly_spike: LineString<f64> = vec![(0.0, 0.0), (100.0, 0.5), (0.0, 1.0)].into();
+        let clamped = poly_spike.clamp_inside(&p(101.0, 10.0));
+        assert!(poly_spike.within(&clamped));
+        assert!((clamped.x - 100.0).abs() < 1e-6 && (clamped.y - 0.5).abs() < 1e-6);
+
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+            ])],
+        );
+        let clamped = poly_square_hole.clamp_inside(&p(5.0, 7.0));
+        assert!(poly_square_hole.within(&clamped));
+        assert!((clamped.y - 7.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn nearest_boundary_point_square() {
+        let poly_square = square();
+        assert_eq!(
+            poly_square.nearest_boundary_point(&p(5.0, 15.0)),
+            p(5.0, 10.0)
+        );
+        assert_eq!(
+            poly_square.nearest_boundary_point(&p(5.0, 9.0)),
+            p(5.0, 10.0)
+        );
+        assert_eq!(
+            poly_square.nearest_boundary_point(&p(15.0, 15.0)),
+            p(10.0, 10.0)
+        );
+        assert_eq!(
+            LineString::<f64>(vec![]).nearest_boundary_point(&p(5.0, 15.0)),
+            p(5.0, 15.0)
+        );
+    }
+
+    #[test]
+    fn nearest_boundary_point_square_hole() {
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        assert_eq!(
+            poly_square_hole.nearest_boundary_point(&p(5.0, 15.0)),
+            p(5.0, 10.0)
+        );
+        assert_eq!(
+            poly_square_hole.nearest_boundary_point(&p(5.0, 6.0)),
+            p(5.0, 7.5)
+        );
+        assert_eq!(
+            poly_square_hole.nearest_boundary_point(&p(5.0, 8.0)),
+            p(5.0, 7.5)
+        );
+    }
+}
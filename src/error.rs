@@ -0,0 +1,99 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Error type for fallible operations
+
+use core::fmt;
+
+/// Reasons an operation can't produce a meaningful result
+///
+/// This covers every fallible operation of the crate, parsing included: the errors of the
+/// underlying parsers are wrapped in the variants of their feature, and returned by
+/// `Error::source`.
+#[derive(Debug)]
+pub enum RayCastError {
+    /// A coordinate of the query point or of the geometry is NaN or infinite
+    NonFiniteCoordinate,
+    /// A ring has no coordinates at all
+    EmptyRing,
+    /// The exterior ring encloses no area, having fewer than 3 distinct points or only collinear
+    /// ones
+    DegeneratePolygon,
+    /// A hole crosses or touches the exterior ring
+    CrossingRings,
+    /// A hole lies outside the exterior ring
+    HoleOutsideExterior,
+    /// Two holes cross, touch, or one lies inside the other
+    OverlappingHoles,
+    /// A ring crosses or touches itself
+    SelfIntersection,
+    /// Well-Known Text couldn't be parsed as a polygon
+    #[cfg(feature = "wkt")]
+    Wkt(crate::WktError),
+    /// GeoJSON couldn't be read as polygons
+    #[cfg(feature = "geojson")]
+    GeoJson(geojson::Error),
+    /// CSV couldn't be read as coordinates
+    #[cfg(feature = "csv")]
+    Csv(crate::CsvError),
+}
+
+impl fmt::Display for RayCastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RayCastError::NonFiniteCoordinate => f.write_str("coordinate is NaN or infinite"),
+            RayCastError::EmptyRing => f.write_str("ring has no coordinates"),
+            RayCastError::DegeneratePolygon => f.write_str("polygon encloses no area"),
+            RayCastError::CrossingRings => f.write_str("hole crosses the exterior ring"),
+            RayCastError::HoleOutsideExterior => f.write_str("hole lies outside the exterior ring"),
+            RayCastError::OverlappingHoles => f.write_str("holes overlap each other"),
+            RayCastError::SelfIntersection => f.write_str("ring intersects itself"),
+            #[cfg(feature = "wkt")]
+            RayCastError::Wkt(error) => write!(f, "invalid WKT: {}", error),
+            #[cfg(feature = "geojson")]
+            RayCastError::GeoJson(error) => write!(f, "invalid GeoJSON: {}", error),
+            #[cfg(feature = "csv")]
+            RayCastError::Csv(error) => write!(f, "invalid CSV: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RayCastError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "wkt")]
+            RayCastError::Wkt(error) => Some(error),
+            #[cfg(feature = "geojson")]
+            RayCastError::GeoJson(error) => Some(error),
+            #[cfg(feature = "csv")]
+            RayCastError::Csv(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "wkt")]
+impl From<crate::WktError> for RayCastError {
+    fn from(error: crate::WktError) -> Self {
+        RayCastError::Wkt(error)
+    }
+}
+
+#[cfg(feature = "geojson")]
+impl From<geojson::Error> for RayCastError {
+    fn from(error: geojson::Error) -> Self {
+        RayCastError::GeoJson(error)
+    }
+}
+
+#[cfg(feature = "csv")]
+impl From<crate::CsvError> for RayCastError {
+    fn from(error: crate::CsvError) -> Self {
+        RayCastError::Csv(error)
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Stack allocated polygon rings
+
+use geo_types::{Coord, CoordFloat, Line, Point};
+
+use crate::{bounding_rect, pt_in_lines, ray_misses_rect, RayCasting};
+
+/// Ring of `N` coordinates stored inline, with no heap allocation
+///
+/// It's read exactly like a `LineString` ring: it's closed virtually when the last coordinate
+/// isn't the first one, and degenerate rings contain no point, so both give the same results.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedPolygon<T: CoordFloat, const N: usize>(pub [Coord<T>; N]);
+
+impl<T: CoordFloat, const N: usize> FixedPolygon<T, N> {
+    /// Wraps an array of coordinates
+    pub fn new(coords: [Coord<T>; N]) -> Self {
+        FixedPolygon(coords)
+    }
+
+    /// Segments of the ring, including the closing one and skipping the zero-length ones
+    fn edges(&self) -> impl Iterator<Item = Line<T>> + '_ {
+        let closing = match (self.0.first(), self.0.last()) {
+            (Some(first), Some(last)) if first != last => Some(Line::new(*last, *first)),
+            _ => None,
+        };
+        self.0
+            .windows(2)
+            .map(|pair| Line::new(pair[0], pair[1]))
+            .chain(closing)
+            .filter(|line| line.start != line.end)
+    }
+
+    /// Checks if a point is within the ring, with the same result as `RayCasting::within`
+    pub fn contains(&self, pt: &Coord<T>) -> bool {
+        match bounding_rect(&self.0) {
+            Some(rect) if !ray_misses_rect(pt, &rect) => pt_in_lines(pt, self.edges()),
+            _ => false,
+        }
+    }
+}
+
+impl<T: CoordFloat, const N: usize> From<[Coord<T>; N]> for FixedPolygon<T, N> {
+    fn from(coords: [Coord<T>; N]) -> Self {
+        FixedPolygon::new(coords)
+    }
+}
+
+impl<T: CoordFloat, const N: usize> RayCasting<T, Point<T>> for FixedPolygon<T, N> {
+    fn within(&self, pt: &Point<T>) -> bool {
+        self.contains(&pt.0)
+    }
+}
+
+impl<T: CoordFloat, const N: usize> RayCasting<T, Coord<T>> for FixedPolygon<T, N> {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        self.contains(pt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FixedPolygon;
+    use crate::RayCasting;
+
+    use geo_types::{Coord, LineString, Point};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        Coord { x, y }
+    }
+
+    #[test]
+    fn fixed_square() {
+        let fixed: FixedPolygon<f64, 5> = FixedPolygon::new([
+            p(0.0, 0.0),
+            p(1.0, 0.0),
+            p(1.0, 1.0),
+            p(0.0, 1.0),
+            p(0.0, 0.0),
+        ]);
+        let ring = LineString(fixed.0.to_vec());
+        let open = FixedPolygon::from([p(0.0, 0.0), p(1.0, 0.0), p(1.0, 1.0), p(0.0, 1.0)]);
+        for x in -4..=8 {
+            for y in -4..=8 {
+                let pt = p(f64::from(x) * 0.25, f64::from(y) * 0.25);
+                assert_eq!(fixed.within(&pt), ring.within(&pt), "{:?}", pt);
+                assert_eq!(open.within(&pt), ring.within(&pt), "{:?}", pt);
+            }
+        }
+        assert!(fixed.within(&Point::new(0.5, 0.5)));
+        assert!(!fixed.within(&Point::new(1.5, 0.5)));
+    }
+
+    #[test]
+    fn fixed_degenerate() {
+        let line = FixedPolygon::from([p(0.0, 0.0), p(5.0, 5.0), p(10.0, 10.0)]);
+        assert!(!line.within(&p(5.0, 5.0)));
+        let empty = FixedPolygon::<f64, 0>::new([]);
+        assert!(!empty.within(&p(0.0, 0.0)));
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Polygon construction from CSV coordinates
+
+use std::io::Read;
+
+use csv::{ReaderBuilder, Trim};
+use geo_types::{Coord, Polygon};
+
+use crate::{ring_from_points, RayCastError};
+
+/// Error of the CSV reader, wrapped in `RayCastError::Csv`
+pub use csv::Error as CsvError;
+
+/// Reads the exterior ring of a polygon from CSV rows of two columns, `x,y`, without a header
+///
+/// Further columns are ignored, and the ring is closed if the last row doesn't repeat the first
+/// one.
+pub fn polygon_from_csv(reader: impl Read) -> Result<Polygon<f64>, RayCastError> {
+    let coords = ReaderBuilder::new()
+        .has_headers(false)
+        .trim(Trim::All)
+        .from_reader(reader)
+        .deserialize()
+        .map(|row| row.map(|(x, y)| Coord { x, y }))
+        .collect::<Result<_, CsvError>>()?;
+    Ok(Polygon::new(ring_from_points(coords), vec![]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::polygon_from_csv;
+    use crate::{RayCastError, RayCasting};
+
+    use geo_types::{Coord, LineString};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        (x, y).into()
+    }
+
+    #[test]
+    fn csv_square() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        let csv = poly_square
+            .coords()
+            .map(|c| format!("{},{}\n", c.x, c.y))
+            .collect::<String>();
+        let poly = polygon_from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(poly.exterior(), &poly_square);
+        assert!(poly.interiors().is_empty());
+        assert!(poly.within(&p(5.0, 5.0)));
+        assert!(poly.within(&p(0.0, 5.0)));
+        assert!(!poly.within(&p(10.0, 5.0)));
+        assert!(!poly.within(&p(-10.0, 5.0)));
+
+        // the ring is closed when the last row is missing
+        let open = polygon_from_csv("0, 0\n10, 0\n10, 10\n0, 10\n".as_bytes()).unwrap();
+        assert_eq!(open.exterior(), &poly_square);
+        let extra = polygon_from_csv("0,0,a\n10,0,b\n10,10,c\n0,10,d\n".as_bytes()).unwrap();
+        assert_eq!(extra.exterior(), &poly_square);
+    }
+
+    #[test]
+    fn csv_invalid() {
+        assert!(matches!(
+            polygon_from_csv("0,0\n10,zero\n10,10\n".as_bytes()),
+            Err(RayCastError::Csv(_))
+        ));
+        let error = polygon_from_csv("0,0\n10\n10,10\n".as_bytes()).unwrap_err();
+        assert!(matches!(error, RayCastError::Csv(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Polygon extraction from GeoJSON geometries
+
+use std::convert::TryFrom;
+
+use geo_types::{MultiPolygon, Polygon};
+use geojson::{Error, Geometry, GeometryValue};
+
+use crate::RayCastError;
+
+/// Extracts the polygons of a GeoJSON `Polygon`, `MultiPolygon` or `GeometryCollection` of those
+///
+/// Any other geometry type, even when nested in a collection, is reported as
+/// `RayCastError::GeoJson` wrapping `Error::InvalidGeometryConversion` rather than skipped.
+pub fn from_geojson(value: &Geometry) -> Result<Vec<Polygon<f64>>, RayCastError> {
+    match &value.value {
+        GeometryValue::Polygon { .. } => Ok(vec![Polygon::try_from(value)?]),
+        GeometryValue::MultiPolygon { .. } => Ok(MultiPolygon::try_from(value)?.0),
+        GeometryValue::GeometryCollection { geometries } => {
+            let mut polygons = Vec::new();
+            for geometry in geometries {
+                polygons.extend(from_geojson(geometry)?);
+            }
+            Ok(polygons)
+        }
+        other => Err(RayCastError::GeoJson(Error::InvalidGeometryConversion {
+            expected_type: "Polygon or MultiPolygon",
+            found_type: other.type_name(),
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_geojson;
+    use crate::{locate_in, RayCastError, RayCasting};
+
+    use std::convert::TryFrom;
+
+    use geo_types::Coord;
+    use geojson::{Error, FeatureCollection, GeoJson, Geometry};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        Coord { x, y }
+    }
+
+    const ZONES: &str = r#"{
+        "type": "FeatureCollection",
+        "features": [
+            {
+                "type": "Feature",
+                "properties": { "name": "square" },
+                "geometry": {
+                    "type": "Polygon",
+                    "coordinates": [
+                        [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0], [0.0, 0.0]],
+                        [[2.5, 2.5], [7.5, 2.5], [7.5, 7.5], [2.5, 7.5], [2.5, 2.5]]
+                    ]
+                }
+            },
+            {
+                "type": "Feature",
+                "properties": { "name": "islands" },
+                "geometry": {
+                    "type": "MultiPolygon",
+                    "coordinates": [
+                        [[[20.0, 0.0], [30.0, 0.0], [30.0, 10.0], [20.0, 0.0]]],
+                        [[[40.0, 0.0], [50.0, 0.0], [50.0, 10.0], [40.0, 0.0]]]
+                    ]
+                }
+            },
+            {
+                "type": "Feature",
+                "properties": { "name": "pin" },
+                "geometry": { "type": "Point", "coordinates": [5.0, 5.0] }
+            }
+        ]
+    }"#;
+
+    fn geometries() -> Vec<Geometry> {
+        let geojson = ZONES.parse::<GeoJson>().unwrap();
+        FeatureCollection::try_from(geojson)
+            .unwrap()
+            .features
+            .into_iter()
+            .map(|feature| feature.geometry.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn geojson_polygons() {
+        let geometries = geometries();
+
+        let square = from_geojson(&geometries[0]).unwrap();
+        assert_eq!(square.len(), 1);
+        assert!(square[0].within(&p(5.0, 8.0)));
+        assert!(!square[0].within(&p(5.0, 5.0)));
+
+        let islands = from_geojson(&geometries[1]).unwrap();
+        assert_eq!(islands.len(), 2);
+        assert_eq!(locate_in(&p(45.0, 2.0), &islands), Some(1));
+    }
+
+    #[test]
+    fn geojson_unsupported() {
+        let geometries = geometries();
+        assert!(matches!(
+            from_geojson(&geometries[2]),
+            Err(RayCastError::GeoJson(Error::InvalidGeometryConversion {
+                found_type: "Point",
+                ..
+            }))
+        ));
+
+        let collection = Geometry::new(geojson::GeometryValue::GeometryCollection { geometries });
+        let error = from_geojson(&collection).unwrap_err();
+        assert!(matches!(error, RayCastError::GeoJson(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
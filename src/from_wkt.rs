@@ -0,0 +1,56 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Polygon construction from Well-Known Text
+
+use geo_types::Polygon;
+use wkt::TryFromWkt;
+
+use crate::RayCastError;
+
+/// Error of the Well-Known Text parser, wrapped in `RayCastError::Wkt`
+pub use wkt::geo_types_from_wkt::Error as WktError;
+
+/// Parses a `POLYGON` in Well-Known Text, like `POLYGON((0 0, 10 0, 10 10, 0 10, 0 0))`
+pub fn polygon_from_wkt(s: &str) -> Result<Polygon<f64>, RayCastError> {
+    Ok(Polygon::try_from_wkt_str(s)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::polygon_from_wkt;
+    use crate::{RayCastError, RayCasting};
+
+    use geo_types::Coord;
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        (x, y).into()
+    }
+
+    #[test]
+    fn wkt_square_hole() {
+        let poly_square_hole = polygon_from_wkt(
+            "POLYGON((0 0, 10 0, 10 10, 0 10, 0 0), (2.5 2.5, 7.5 2.5, 7.5 7.5, 2.5 7.5, 2.5 2.5))",
+        )
+        .unwrap();
+        assert!(!poly_square_hole.within(&p(5.0, 5.0)));
+        assert!(poly_square_hole.within(&p(5.0, 8.0)));
+        assert!(!poly_square_hole.within(&p(-10.0, 5.0)));
+        assert!(poly_square_hole.within(&p(8.0, 5.0)));
+    }
+
+    #[test]
+    fn wkt_invalid() {
+        assert!(matches!(
+            polygon_from_wkt("POLYGON((0 0, 10 0, 10 10"),
+            Err(RayCastError::Wkt(_))
+        ));
+        let error = polygon_from_wkt("POINT(5 5)").unwrap_err();
+        assert!(matches!(error, RayCastError::Wkt(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
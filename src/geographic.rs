@@ -0,0 +1,546 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Containment for geographic coordinates on the sphere
+//!
+//! Coordinates are expected in degrees, with `x` holding the longitude and `y` the latitude, as
+//! `geo_types` and GeoJSON do. Beware that the planar tests of this crate happen to store
+//! latitude first, which is irrelevant to `RayCasting::within` but not to this module.
+
+use alloc::vec::Vec;
+
+use geo_types::{Coord, CoordFloat, Line, LineString, Polygon};
+
+use crate::{bounding_rect, pt_in_polygon, ring_lines};
+
+/// Trait implementing Ray Casting on the sphere, for longitude/latitude coordinates
+pub trait Geographic<T: CoordFloat, P: Into<Coord<T>>> {
+    /// Checks if a point is within a polygonal area whose edges are great-circle arcs
+    ///
+    /// The ray runs along the point's meridian towards the north pole, so edges may freely cross
+    /// the ±180° meridian, but areas enclosing the north pole report inverted results.
+    fn within_geographic(&self, pt: &P) -> bool;
+
+    /// Checks if a point is within a polygonal area with planar edges, allowing rings to cross
+    /// the ±180° meridian
+    ///
+    /// Edges spanning more than 180° of longitude are taken as crossing the antimeridian: such
+    /// rings are unwrapped into a continuous longitude range, and the point is moved by a whole
+    /// turn to the copy nearest to the ring, before running the planar ray cast.
+    fn within_wrapped(&self, pt: &P) -> bool;
+
+    /// Great-circle distance in meters from a point to the nearest edge, negative when the point
+    /// is within the area as reported by `within_geographic`
+    ///
+    /// Edges are great-circle arcs, and distances are measured on a sphere with the mean earth
+    /// radius of 6371008.8 meters, with the haversine formula. An empty area has no edge, in which
+    /// case the distance is infinite.
+    fn signed_distance_haversine(&self, pt: &P) -> T;
+}
+
+/// Trait inserting vertices along great-circle edges, so that planar tests follow them
+///
+/// `RayCasting::within` joins vertices with straight lines in the longitude/latitude plane,
+/// which drift away from the great-circle arcs as edges get longer. Densifying first brings the
+/// planar ring as close to the arcs as needed, at the price of more segments to test.
+pub trait Densify<T: CoordFloat> {
+    /// Copy where every edge is split into equal great-circle pieces no longer than
+    /// `max_segment_length` degrees, as measured in the longitude/latitude plane
+    ///
+    /// The inserted vertices lie on the great-circle arc between the original ones, taken the
+    /// short way around, and original vertices are kept. A ring that isn't explicitly closed
+    /// stays open, with its closing edge densified too. A non-positive or NaN length leaves the
+    /// geometry unchanged.
+    ///
+    /// Every edge is split in at most 65536 pieces: a length too small for that, down to the
+    /// smallest positive float, yields 65536 pieces longer than asked rather than exhausting
+    /// memory. Edges with a non-finite coordinate are kept as they are.
+    fn densify(&self, max_segment_length: T) -> Self;
+}
+
+/// Mean earth radius in meters
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Maximum number of pieces `Densify::densify` splits an edge in
+const MAX_PIECES: usize = 1 << 16;
+
+/// Wraps a longitude difference in degrees into `(-180, 180]`
+fn wrap<T: CoordFloat>(delta: T) -> T {
+    let half = T::from(180.0).unwrap();
+    let full = half + half;
+    let wrapped = delta - (delta / full).round() * full;
+    if wrapped <= -half {
+        wrapped + full
+    } else {
+        wrapped
+    }
+}
+
+/// Checks if the northward meridian ray from `pt` crosses the great-circle arc `line`
+fn meridian_crosses_arc<T: CoordFloat>(pt: &Coord<T>, line: &Line<T>) -> bool {
+    let span = wrap(line.end.x - line.start.x);
+    let offset = wrap(pt.x - line.start.x);
+    if (T::zero() <= offset) == (span <= offset) {
+        return false;
+    }
+
+    let (span, offset) = (span.to_radians(), offset.to_radians());
+    let tan_lat = (line.start.y.to_radians().tan() * (span - offset).sin()
+        + line.end.y.to_radians().tan() * offset.sin())
+        / span.sin();
+    tan_lat > pt.y.to_radians().tan()
+}
+
+/// Central angle in radians between two points, with the haversine formula
+fn haversine<T: CoordFloat>(a: &Coord<T>, b: &Coord<T>) -> T {
+    let two = T::one() + T::one();
+    let (lat_a, lat_b) = (a.y.to_radians(), b.y.to_radians());
+    let half_lat = ((lat_b - lat_a) / two).sin();
+    let half_lon = ((b.x - a.x).to_radians() / two).sin();
+    let h = half_lat * half_lat + lat_a.cos() * lat_b.cos() * half_lon * half_lon;
+    two * h.sqrt().min(T::one()).asin()
+}
+
+/// Initial bearing in radians of the great circle from `a` to `b`
+fn bearing<T: CoordFloat>(a: &Coord<T>, b: &Coord<T>) -> T {
+    let (lat_a, lat_b) = (a.y.to_radians(), b.y.to_radians());
+    let delta = (b.x - a.x).to_radians();
+    (delta.sin() * lat_b.cos())
+        .atan2(lat_a.cos() * lat_b.sin() - lat_a.sin() * lat_b.cos() * delta.cos())
+}
+
+/// Central angle in radians between `pt` and the great-circle arc `line`
+///
+/// The cross-track distance is used when `pt` projects within the arc, the distance to the
+/// nearest endpoint otherwise.
+fn arc_distance<T: CoordFloat>(pt: &Coord<T>, line: &Line<T>) -> T {
+    let to_start = haversine(&line.start, pt);
+    let to_end = haversine(&line.end, pt);
+    let length = haversine(&line.start, &line.end);
+    let delta = bearing(&line.start, pt) - bearing(&line.start, &line.end);
+    let cross_track = (to_start.sin() * delta.sin()).asin();
+    let along_track = (to_start.cos() / cross_track.cos())
+        .max(-T::one())
+        .min(T::one())
+        .acos();
+    if delta.cos() > T::zero() && along_track <= length {
+        cross_track.abs()
+    } else {
+        to_start.min(to_end)
+    }
+}
+
+fn ring_distance_haversine<'a, T: CoordFloat + 'a, I: IntoIterator<Item = &'a LineString<T>>>(
+    pt: &Coord<T>,
+    rings: I,
+) -> T {
+    let angle = rings
+        .into_iter()
+        .flat_map(ring_lines)
+        .map(|line| arc_distance(pt, &line))
+        .fold(T::infinity(), T::min);
+    angle * T::from(EARTH_RADIUS).unwrap()
+}
+
+fn signed<T: CoordFloat>(distance: T, within: bool) -> T {
+    if within {
+        -distance
+    } else {
+        distance
+    }
+}
+
+fn pt_in_spherical_ring<T: CoordFloat>(pt: &Coord<T>, ring: &LineString<T>) -> bool {
+    ring_lines(ring)
+        .filter(|line| meridian_crosses_arc(pt, line))
+        .count()
+        % 2
+        == 1
+}
+
+/// Point at `fraction` along the great-circle arc from `a` to `b`
+fn interpolate_arc<T: CoordFloat>(a: &Coord<T>, b: &Coord<T>, fraction: T) -> Coord<T> {
+    let angle = haversine(a, b);
+    let sin = angle.sin();
+    if sin == T::zero() {
+        // same or antipodal points, no single arc joins them
+        return *a + (*b - *a) * fraction;
+    }
+    let vector = |c: &Coord<T>| {
+        let (lat, lon) = (c.y.to_radians(), c.x.to_radians());
+        (lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin())
+    };
+    let (va, vb) = (vector(a), vector(b));
+    let wa = ((T::one() - fraction) * angle).sin() / sin;
+    let wb = (fraction * angle).sin() / sin;
+    let (x, y, z) = (
+        wa * va.0 + wb * vb.0,
+        wa * va.1 + wb * vb.1,
+        wa * va.2 + wb * vb.2,
+    );
+    Coord {
+        x: y.atan2(x).to_degrees(),
+        y: z.atan2(x.hypot(y)).to_degrees(),
+    }
+}
+
+fn densify_ring<T: CoordFloat>(ring: &LineString<T>, max_segment_length: T) -> LineString<T> {
+    let mut coords = Vec::with_capacity(ring.0.len());
+    for line in ring_lines(ring) {
+        coords.push(line.start);
+        let length = wrap(line.end.x - line.start.x).hypot(line.end.y - line.start.y);
+        let ratio = (length / max_segment_length).ceil();
+        let pieces = if ratio.is_nan() {
+            1
+        } else {
+            // an infinite ratio doesn't fit either
+            ratio.to_usize().unwrap_or(MAX_PIECES).clamp(1, MAX_PIECES)
+        };
+        let total = T::from(pieces).unwrap();
+        coords.extend(
+            (1..pieces).map(|piece| {
+                interpolate_arc(&line.start, &line.end, T::from(piece).unwrap() / total)
+            }),
+        );
+    }
+    if ring.is_closed() {
+        coords.extend(ring.0.last());
+    }
+    LineString(coords)
+}
+
+fn crosses_antimeridian<T: CoordFloat>(ring: &LineString<T>) -> bool {
+    let half = T::from(180.0).unwrap();
+    ring_lines(ring).any(|line| (line.end.x - line.start.x).abs() > half)
+}
+
+/// Makes longitudes continuous along the ring, so that no edge spans more than 180°
+fn unwrap_ring<T: CoordFloat>(ring: &LineString<T>) -> LineString<T> {
+    let mut prev: Option<(T, T)> = None;
+    ring.coords()
+        .map(|c| {
+            let x = match prev {
+                Some((orig, unwrapped)) => unwrapped + wrap(c.x - orig),
+                None => c.x,
+            };
+            prev = Some((c.x, x));
+            Coord { x, y: c.y }
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn pt_in_wrapped_ring<T: CoordFloat>(pt: &Coord<T>, ring: &LineString<T>) -> bool {
+    if !crosses_antimeridian(ring) {
+        return pt_in_polygon(pt, ring);
+    }
+
+    let ring = unwrap_ring(ring);
+    let center = match bounding_rect(ring.coords()) {
+        Some(rect) => rect.center().x,
+        None => return false,
+    };
+    let pt = Coord {
+        x: center + wrap(pt.x - center),
+        y: pt.y,
+    };
+    pt_in_polygon(&pt, &ring)
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> Geographic<T, P> for LineString<T> {
+    fn within_geographic(&self, pt: &P) -> bool {
+        pt_in_spherical_ring(&(*pt).into(), self)
+    }
+
+    fn within_wrapped(&self, pt: &P) -> bool {
+        pt_in_wrapped_ring(&(*pt).into(), self)
+    }
+
+    fn signed_distance_haversine(&self, pt: &P) -> T {
+        let coord = (*pt).into();
+        signed(
+            ring_distance_haversine(&coord, core::iter::once(self)),
+            pt_in_spherical_ring(&coord, self),
+        )
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> Geographic<T, P> for Polygon<T> {
+    fn within_geographic(&self, pt: &P) -> bool {
+        let coord = (*pt).into();
+        pt_in_spherical_ring(&coord, self.exterior())
+            && !self
+                .interiors()
+                .iter()
+                .any(|ring| pt_in_spherical_ring(&coord, ring))
+    }
+
+    fn within_wrapped(&self, pt: &P) -> bool {
+        let coord = (*pt).into();
+        pt_in_wrapped_ring(&coord, self.exterior())
+            && !self
+                .interiors()
+                .iter()
+                .any(|ring| pt_in_wrapped_ring(&coord, ring))
+    }
+
+    fn signed_distance_haversine(&self, pt: &P) -> T {
+        let coord = (*pt).into();
+        signed(
+            ring_distance_haversine(
+                &coord,
+                core::iter::once(self.exterior()).chain(self.interiors()),
+            ),
+            self.within_geographic(&coord),
+        )
+    }
+}
+
+impl<T: CoordFloat> Densify<T> for LineString<T> {
+    fn densify(&self, max_segment_length: T) -> Self {
+        if max_segment_length > T::zero() {
+            densify_ring(self, max_segment_length)
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl<T: CoordFloat> Densify<T> for Polygon<T> {
+    fn densify(&self, max_segment_length: T) -> Self {
+        Polygon::new(
+            self.exterior().densify(max_segment_length),
+            self.interiors()
+                .iter()
+                .map(|ring| ring.densify(max_segment_length))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Densify, Geographic, MAX_PIECES};
+    use crate::RayCasting;
+
+    use geo_types::{Coord, LineString, Polygon};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        (x, y).into()
+    }
+
+    #[test]
+    fn geographic_great_circle() {
+        let poly_cap: LineString<f64> = vec![
+            (-60.0, 0.0),
+            (60.0, 0.0),
+            (60.0, 60.0),
+            (-60.0, 60.0),
+            (-60.0, 0.0),
+        ]
+        .into();
+        // the top edge bulges north up to about 73.9° at the central meridian
+        assert!(!poly_cap.within(&p(0.0, 65.0)));
+        assert!(poly_cap.within_geographic(&p(0.0, 65.0)));
+        assert!(!poly_cap.within_geographic(&p(0.0, 75.0)));
+        assert!(poly_cap.within_geographic(&p(0.0, 30.0)));
+        assert!(!poly_cap.within_geographic(&p(0.0, -5.0)));
+        assert!(!poly_cap.within_geographic(&p(90.0, 30.0)));
+    }
+
+    #[test]
+    fn geographic_antimeridian() {
+        let poly_pacific = Polygon::new(
+            LineString::from(vec![
+                (170.0, -10.0),
+                (-170.0, -10.0),
+                (-170.0, 10.0),
+                (170.0, 10.0),
+                (170.0, -10.0),
+            ]),
+            vec![],
+        );
+        assert!(poly_pacific.within_geographic(&p(179.0, 0.0)));
+        assert!(poly_pacific.within_geographic(&p(-179.0, 0.0)));
+        assert!(poly_pacific.within_geographic(&p(180.0, 0.0)));
+        assert!(!poly_pacific.within_geographic(&p(0.0, 0.0)));
+        assert!(!poly_pacific.within_geographic(&p(160.0, 0.0)));
+        assert!(!poly_pacific.within_geographic(&p(179.0, 20.0)));
+    }
+
+    #[test]
+    fn wrapped_antimeridian() {
+        let poly_pacific = Polygon::new(
+            LineString::from(vec![
+                (170.0, -10.0),
+                (-170.0, -10.0),
+                (-170.0, 10.0),
+                (170.0, 10.0),
+                (170.0, -10.0),
+            ]),
+            vec![LineString::from(vec![
+                (178.0, -2.0),
+                (-178.0, -2.0),
+                (-178.0, 2.0),
+                (178.0, 2.0),
+                (178.0, -2.0),
+            ])],
+        );
+        // the planar ray cast sees a polygon spanning the whole globe but the Pacific
+        assert!(!poly_pacific.within(&p(179.0, 5.0)));
+        assert!(poly_pacific.within(&p(0.0, 5.0)));
+        assert!(poly_pacific.within_wrapped(&p(179.0, 5.0)));
+        assert!(poly_pacific.within_wrapped(&p(-175.0, 5.0)));
+        assert!(poly_pacific.within_wrapped(&p(539.0, 5.0)));
+        assert!(!poly_pacific.within_wrapped(&p(179.0, 0.0)));
+        assert!(!poly_pacific.within_wrapped(&p(0.0, 5.0)));
+        assert!(!poly_pacific.within_wrapped(&p(160.0, 5.0)));
+        assert!(!poly_pacific.within_wrapped(&p(179.0, 20.0)));
+    }
+
+    #[test]
+    fn wrapped_regular() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert!(poly_square.within_wrapped(&p(5.0, 5.0)));
+        assert!(!poly_square.within_wrapped(&p(365.0, 5.0)));
+        assert!(!poly_square.within_wrapped(&p(-10.0, 5.0)));
+    }
+
+    #[test]
+    fn geographic_real_coords() {
+        // the cell from the planar tests, with coordinates swapped to longitude first
+        let cell = LineString::from(vec![
+            (11.9147053956319, 45.3563321662796),
+            (11.9455630525467, 45.4293499926637),
+            (11.8515426867682, 45.4392542159797),
+            (11.8209138798751, 45.3661863570488),
+        ]);
+        assert!(cell.within_geographic(&p(11.887047957258, 45.429671680421)));
+        assert!(cell.within_geographic(&p(11.833525908467, 45.395726701315)));
+        assert!(!cell.within_geographic(&p(11.811773142492, 45.414838131946)));
+    }
+
+    #[test]
+    fn haversine_distance() {
+        let poly_cell: LineString<f64> =
+            vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)].into();
+        // the bottom edge runs along the equator, 0.1° south of it is 0.1° of a great circle
+        let expected = 0.1_f64.to_radians() * 6_371_008.8;
+        let distance = poly_cell.signed_distance_haversine(&p(0.5, -0.1));
+        assert!((distance - expected).abs() < 1e-6, "{}", distance);
+        let distance = poly_cell.signed_distance_haversine(&p(-0.1, 0.0));
+        assert!((distance - expected).abs() < 1e-6, "{}", distance);
+        let distance = poly_cell.signed_distance_haversine(&p(0.5, 0.1));
+        assert!(
+            distance < 0.0 && (distance + expected).abs() < 1.0,
+            "{}",
+            distance
+        );
+        let distance = poly_cell.signed_distance_haversine(&p(0.5, 0.5));
+        assert!(distance < -55_000.0 && distance > -55_600.0, "{}", distance);
+        assert_eq!(
+            LineString::<f64>(vec![]).signed_distance_haversine(&p(0.0, 0.0)),
+            f64::INFINITY
+        );
+
+        let poly_pacific = Polygon::new(
+            LineString::from(vec![
+                (170.0, -10.0),
+                (-170.0, -10.0),
+                (-170.0, 10.0),
+                (170.0, 10.0),
+            ]),
+            vec![LineString::from(vec![
+                (178.0, -2.0),
+                (-178.0, -2.0),
+                (-178.0, 2.0),
+                (178.0, 2.0),
+            ])],
+        );
+        // 1° east of the hole's edge across the antimeridian, along the equator
+        let expected = 1_f64.to_radians() * 6_371_008.8;
+        let distance = poly_pacific.signed_distance_haversine(&p(179.0, 0.0));
+        assert!((distance - expected).abs() < 1e-6, "{}", distance);
+        let distance = poly_pacific.signed_distance_haversine(&p(-177.0, 0.0));
+        assert!((distance + expected).abs() < 1e-6, "{}", distance);
+    }
+
+    #[test]
+    fn densify_edges() {
+        let poly_cell: LineString<f64> =
+            vec![(0.0, 0.0), (10.0, 0.0), (10.0, 1.0), (0.0, 1.0), (0.0, 0.0)].into();
+        let dense = poly_cell.densify(1.0);
+        assert_eq!(dense.0.len(), 23);
+        assert!(dense.is_closed());
+        let finer = poly_cell.densify(0.5);
+        assert_eq!(finer.0.len(), 45);
+        // along the equator the arc is the straight edge
+        for (i, c) in dense.0[..10].iter().enumerate() {
+            assert!((c.x - i as f64).abs() < 1e-9 && c.y.abs() < 1e-9, "{:?}", c);
+        }
+        assert_eq!(poly_cell.densify(0.0), poly_cell);
+        assert_eq!(poly_cell.densify(f64::NAN), poly_cell);
+
+        let open: LineString<f64> = vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0)].into();
+        // the closing edge is about 2.83 long, so it gets split in 3
+        let dense = open.densify(1.0);
+        assert_eq!(dense.0.len(), 7);
+        assert!(!dense.is_closed());
+    }
+
+    #[test]
+    fn densify_clamped() {
+        let poly_cell: LineString<f64> =
+            vec![(0.0, 0.0), (10.0, 0.0), (10.0, 1.0), (0.0, 1.0), (0.0, 0.0)].into();
+        let clamped = 4 * MAX_PIECES + 1;
+        let tiny = poly_cell.densify(1e-12);
+        assert_eq!(tiny.0.len(), clamped);
+        assert_eq!(tiny.0[MAX_PIECES], p(10.0, 0.0));
+        // the ratio overflows usize, it's clamped all the same
+        let smallest = poly_cell.densify(f64::MIN_POSITIVE);
+        assert_eq!(smallest.0.len(), clamped);
+        assert!(smallest.is_closed());
+
+        // only the closing edge, 3 long, is split
+        let poly_nan: LineString<f64> = vec![(0.0, 0.0), (10.0, f64::NAN), (0.0, 3.0)].into();
+        assert_eq!(poly_nan.densify(1.0).0.len(), 5);
+    }
+
+    #[test]
+    fn densify_great_circle() {
+        let poly_cap = Polygon::new(
+            LineString::from(vec![
+                (-60.0, 0.0),
+                (60.0, 0.0),
+                (60.0, 60.0),
+                (-60.0, 60.0),
+                (-60.0, 0.0),
+            ]),
+            vec![],
+        );
+        let dense = poly_cap.densify(1.0);
+        // the planar test now follows the top edge bulging north
+        assert!(!poly_cap.within(&p(0.0, 65.0)));
+        assert!(dense.within(&p(0.0, 65.0)));
+        assert!(!dense.within(&p(0.0, 75.0)));
+        for pt in [p(0.0, 65.0), p(0.0, 75.0), p(50.0, 62.0), p(0.0, 30.0)] {
+            assert_eq!(
+                dense.within(&pt),
+                poly_cap.within_geographic(&pt),
+                "{:?}",
+                pt
+            );
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Queries on the rings of a polygon taken apart
+
+use geo_types::{Coord, CoordFloat, Polygon};
+
+use crate::pt_in_polygon;
+
+/// Trait querying the exterior and the holes of a polygon separately, where
+/// `RayCasting::within` combines them
+pub trait Holes<T: CoordFloat, P: Into<Coord<T>>> {
+    /// Index of the first hole containing a point, if any
+    ///
+    /// Holes are tested like rings by `RayCasting::within`, regardless of the exterior, so this
+    /// explains why a point within the exterior is reported outside the polygon.
+    fn hole_containing(&self, pt: &P) -> Option<usize>;
+
+    /// Checks if a point is within the exterior ring, ignoring the holes
+    fn within_exterior(&self, pt: &P) -> bool;
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> Holes<T, P> for Polygon<T> {
+    fn hole_containing(&self, pt: &P) -> Option<usize> {
+        let coord = (*pt).into();
+        self.interiors()
+            .iter()
+            .position(|ring| pt_in_polygon(&coord, ring))
+    }
+
+    fn within_exterior(&self, pt: &P) -> bool {
+        pt_in_polygon(&(*pt).into(), self.exterior())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Holes;
+    use crate::RayCasting;
+
+    use geo_types::{Coord, LineString, Point, Polygon};
+
+    fn square(min: f64, max: f64) -> LineString<f64> {
+        LineString::from(vec![
+            (min, min),
+            (max, min),
+            (max, max),
+            (min, max),
+            (min, min),
+        ])
+    }
+
+    #[test]
+    fn hole_containing_square_hole() {
+        let poly_square_hole = Polygon::new(square(0.0, 10.0), vec![square(2.5, 7.5)]);
+        assert_eq!(
+            poly_square_hole.hole_containing(&Coord::from((5.0, 5.0))),
+            Some(0)
+        );
+        assert_eq!(
+            poly_square_hole.hole_containing(&Point::new(5.0, 8.0)),
+            None
+        );
+        assert_eq!(poly_square_hole.hole_containing(&(-10.0, 5.0)), None);
+
+        let poly_two_holes =
+            Polygon::new(square(0.0, 10.0), vec![square(1.0, 2.0), square(5.0, 8.0)]);
+        assert_eq!(poly_two_holes.hole_containing(&(1.5, 1.5)), Some(0));
+        assert_eq!(poly_two_holes.hole_containing(&(6.0, 6.0)), Some(1));
+        assert_eq!(poly_two_holes.hole_containing(&(3.0, 3.0)), None);
+    }
+
+    #[test]
+    fn within_exterior_square_hole() {
+        let poly_square_hole = Polygon::new(square(0.0, 10.0), vec![square(2.5, 7.5)]);
+        let center = Coord::from((5.0, 5.0));
+        assert!(poly_square_hole.within_exterior(&center));
+        assert!(!poly_square_hole.within(&center));
+        assert!(poly_square_hole.within_exterior(&(5.0, 8.0)));
+        assert!(poly_square_hole.within(&Coord::from((5.0, 8.0))));
+        assert!(!poly_square_hole.within_exterior(&Point::new(-10.0, 5.0)));
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! R-tree index over many polygons
+
+use alloc::vec::Vec;
+
+use geo_types::{Coord, CoordFloat, Polygon};
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, RTreeNum};
+
+use crate::{bounding_rect, RayCasting};
+
+type Envelope<T> = GeomWithData<Rectangle<[T; 2]>, usize>;
+
+/// Spatial index answering point queries against many polygons
+///
+/// The exterior bounding boxes are bulk loaded into an R-tree at construction, so a query only
+/// runs the full ray cast on the polygons whose box contains the point. Polygons with an empty
+/// exterior are kept for indexing purposes but never match.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolygonIndex<T: CoordFloat + RTreeNum> {
+    polygons: Vec<Polygon<T>>,
+    tree: RTree<Envelope<T>>,
+}
+
+impl<T: CoordFloat + RTreeNum> PolygonIndex<T> {
+    /// Builds the index, polygon indices follow the order of the given vector
+    pub fn new(polygons: Vec<Polygon<T>>) -> Self {
+        let envelopes = polygons
+            .iter()
+            .enumerate()
+            .filter_map(|(index, poly)| {
+                bounding_rect(poly.exterior().coords()).map(|rect| {
+                    GeomWithData::new(
+                        Rectangle::from_corners(rect.min().x_y().into(), rect.max().x_y().into()),
+                        index,
+                    )
+                })
+            })
+            .collect();
+        PolygonIndex {
+            polygons,
+            tree: RTree::bulk_load(envelopes),
+        }
+    }
+
+    /// Returns the lowest index of a polygon containing a point, like `locate_in`
+    pub fn query(&self, pt: &Coord<T>) -> Option<usize> {
+        self.tree
+            .locate_all_at_point(&[pt.x, pt.y])
+            .map(|envelope| envelope.data)
+            .filter(|index| self.polygons[*index].within(pt))
+            .min()
+    }
+
+    /// Indexed polygons
+    pub fn polygons(&self) -> &[Polygon<T>] {
+        &self.polygons
+    }
+}
+
+impl<T: CoordFloat + RTreeNum> From<Vec<Polygon<T>>> for PolygonIndex<T> {
+    fn from(polygons: Vec<Polygon<T>>) -> Self {
+        PolygonIndex::new(polygons)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PolygonIndex;
+    use crate::locate_in;
+
+    use geo_types::{Coord, LineString, Polygon};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        Coord { x, y }
+    }
+
+    fn grid() -> Vec<Polygon<f64>> {
+        let mut polygons = Vec::new();
+        for i in 0..10 {
+            for j in 0..10 {
+                let (x, y) = (f64::from(i) * 10., f64::from(j) * 10.);
+                polygons.push(Polygon::new(
+                    LineString::from(vec![
+                        (x, y),
+                        (x + 12., y + 1.),
+                        (x + 11., y + 13.),
+                        (x - 1., y + 11.),
+                    ]),
+                    vec![LineString::from(vec![
+                        (x + 4., y + 4.),
+                        (x + 7., y + 4.),
+                        (x + 7., y + 7.),
+                        (x + 4., y + 7.),
+                    ])],
+                ));
+            }
+        }
+        polygons.push(Polygon::new(LineString(vec![]), vec![]));
+        polygons
+    }
+
+    #[test]
+    fn index_matches_linear() {
+        let polygons = grid();
+        let index = PolygonIndex::new(polygons.clone());
+        assert_eq!(index.polygons().len(), polygons.len());
+        for i in -10..=220 {
+            for j in -10..=220 {
+                let pt = p(f64::from(i) * 0.5 + 0.13, f64::from(j) * 0.5 + 0.07);
+                assert_eq!(index.query(&pt), locate_in(&pt, &polygons), "{:?}", pt);
+            }
+        }
+        assert_eq!(index.query(&p(5.5, 5.5)), None);
+        assert_eq!(index.query(&p(2., 5.)), Some(0));
+    }
+
+    #[test]
+    fn index_empty() {
+        let index = PolygonIndex::<f64>::from(vec![]);
+        assert_eq!(index.query(&p(0., 0.)), None);
+    }
+}
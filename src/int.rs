@@ -0,0 +1,240 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Exact ray casting for integer coordinates
+
+use core::cmp::Ordering;
+
+use geo_types::{Coord, CoordNum, LineString, Point, Polygon};
+use num_traits::PrimInt;
+
+use crate::ring_lines;
+
+/// Integer coordinate types supported by `RayCastingInt`, those of 64 bits or fewer
+///
+/// This trait is sealed: the exactness of the cross products relies on every value fitting in an
+/// `i128` with room to spare.
+pub trait IntCoord: CoordNum + PrimInt + private::Sealed {
+    /// Lossless conversion to `i128`
+    fn wide(self) -> i128;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! int_coord {
+    ($($t:ty),*) => {
+        $(
+            impl private::Sealed for $t {}
+
+            impl IntCoord for $t {
+                fn wide(self) -> i128 {
+                    i128::from(self)
+                }
+            }
+        )*
+    };
+}
+
+int_coord!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+/// Ray Casting trait for integer coordinates
+///
+/// Every segment is tested with a cross product sign, like `ray_intersects_segment`, but computed
+/// exactly on integers, for the whole range of any coordinate type up to `i64` and `u64`. Boundary
+/// points follow the same convention as `RayCasting`, left and bottom edges are inside, right and
+/// top edges are outside.
+pub trait RayCastingInt<T: IntCoord, P: Into<Coord<T>>> {
+    /// Checks if a point is within a polygonal area
+    fn within(&self, pt: &P) -> bool;
+}
+
+/// Product of two differences as sign and magnitude
+///
+/// Differences of 64 bits values take 65 bits, so their product may not fit in an `i128`, but its
+/// magnitude always fits in an `u128`.
+fn product(a: i128, b: i128) -> (bool, u128) {
+    let negative = a != 0 && b != 0 && (a < 0) != (b < 0);
+    (negative, a.unsigned_abs() * b.unsigned_abs())
+}
+
+/// Sign of the cross product of `a -> b` and `a -> c`, positive when `c` is left of `a -> b`
+fn orientation<T: IntCoord>(a: &Coord<T>, b: &Coord<T>, c: &Coord<T>) -> Ordering {
+    let (ax, ay) = (a.x.wide(), a.y.wide());
+    let left = product(b.x.wide() - ax, c.y.wide() - ay);
+    let right = product(b.y.wide() - ay, c.x.wide() - ax);
+    match (left, right) {
+        ((false, l), (false, r)) => l.cmp(&r),
+        ((true, l), (true, r)) => r.cmp(&l),
+        ((false, l), (true, r)) => {
+            if l == 0 && r == 0 {
+                Ordering::Equal
+            } else {
+                Ordering::Greater
+            }
+        }
+        ((true, _), (false, _)) => Ordering::Less,
+    }
+}
+
+/// Even-odd test
+///
+/// A segment counts when exactly one of its ends is strictly above the point, so vertices on the
+/// ray height are counted once and horizontal segments never.
+fn pt_in_ring_int<T: IntCoord>(pt: &Coord<T>, ring: &LineString<T>) -> bool {
+    let mut inside = false;
+    for line in ring_lines(ring) {
+        let (start_above, end_above) = (line.start.y > pt.y, line.end.y > pt.y);
+        if start_above != end_above {
+            let side = orientation(&line.start, &line.end, pt);
+            if (end_above && side == Ordering::Greater) || (start_above && side == Ordering::Less) {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn pt_in_polygon_int<T: IntCoord>(pt: &Coord<T>, poly: &Polygon<T>) -> bool {
+    pt_in_ring_int(pt, poly.exterior())
+        && !poly.interiors().iter().any(|ring| pt_in_ring_int(pt, ring))
+}
+
+impl<T: IntCoord> RayCastingInt<T, Point<T>> for LineString<T> {
+    fn within(&self, pt: &Point<T>) -> bool {
+        pt_in_ring_int(&pt.0, self)
+    }
+}
+
+impl<T: IntCoord> RayCastingInt<T, Coord<T>> for LineString<T> {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        pt_in_ring_int(pt, self)
+    }
+}
+
+impl<T: IntCoord> RayCastingInt<T, Point<T>> for Polygon<T> {
+    fn within(&self, pt: &Point<T>) -> bool {
+        pt_in_polygon_int(&pt.0, self)
+    }
+}
+
+impl<T: IntCoord> RayCastingInt<T, Coord<T>> for Polygon<T> {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        pt_in_polygon_int(pt, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RayCastingInt;
+    use crate::RayCasting;
+
+    use geo_types::{Coord, LineString, Point, Polygon};
+
+    fn p(x: i64, y: i64) -> Coord<i64> {
+        Coord { x, y }
+    }
+
+    fn square() -> LineString<i64> {
+        vec![(0, 0), (10, 0), (10, 10), (0, 10), (0, 0)].into()
+    }
+
+    #[test]
+    fn int_square() {
+        let poly_square = square();
+        assert!(poly_square.within(&p(5, 5)));
+        assert!(poly_square.within(&Point::new(5, 8)));
+        assert!(poly_square.within(&p(1, 9)));
+        assert!(!poly_square.within(&p(-10, 5)));
+        assert!(!poly_square.within(&p(-10, 10)));
+        assert!(poly_square.within(&p(0, 5)));
+        assert!(poly_square.within(&p(5, 0)));
+        assert!(poly_square.within(&p(0, 0)));
+        assert!(!poly_square.within(&p(10, 5)));
+        assert!(!poly_square.within(&p(5, 10)));
+        assert!(!poly_square.within(&p(10, 10)));
+        assert!(!poly_square.within(&p(15, 5)));
+    }
+
+    #[test]
+    fn int_hexagon_matches_float() {
+        let coords = vec![(3, 0), (7, 0), (10, 5), (7, 10), (3, 10), (0, 5)];
+        let ring_int: LineString<i64> = coords.clone().into();
+        let ring_float: LineString<f64> = coords
+            .into_iter()
+            .map(|(x, y)| (x as f64, y as f64))
+            .collect::<Vec<_>>()
+            .into();
+        for x in -2..=12 {
+            for y in -2..=12 {
+                let pt = Coord {
+                    x: x as f64,
+                    y: y as f64,
+                };
+                assert_eq!(
+                    RayCastingInt::within(&ring_int, &p(x, y)),
+                    RayCasting::within(&ring_float, &pt),
+                    "{} {}",
+                    x,
+                    y
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn int_square_hole() {
+        let poly = Polygon::new(
+            vec![(0i32, 0), (10, 0), (10, 10), (0, 10)].into(),
+            vec![vec![(2, 2), (8, 2), (8, 8), (2, 8)].into()],
+        );
+        assert!(poly.within(&Coord { x: 1, y: 1 }));
+        assert!(!poly.within(&Coord { x: 5, y: 5 }));
+        assert!(!poly.within(&Coord { x: 2, y: 5 }));
+        assert!(poly.within(&Coord { x: 8, y: 5 }));
+        assert!(!poly.within(&Coord { x: 12, y: 5 }));
+    }
+
+    #[test]
+    fn int_large_coordinates() {
+        let max = i64::MAX / 2;
+        let ring: LineString<i64> = vec![(-max, -max), (max, -max), (max, max), (-max, max)].into();
+        assert!(ring.within(&p(0, 0)));
+        assert!(ring.within(&p(max - 1, max - 1)));
+        assert!(!ring.within(&p(max, 0)));
+        assert!(!ring.within(&p(max + 1, 0)));
+    }
+
+    #[test]
+    fn int_extreme_coordinates() {
+        let (min, max) = (i64::MIN, i64::MAX);
+        let ring: LineString<i64> = vec![(min, min), (max, min), (max, max), (min, max)].into();
+        assert!(ring.within(&p(0, 0)));
+        assert!(ring.within(&p(min, min)));
+        assert!(ring.within(&p(max - 1, max - 1)));
+        assert!(!ring.within(&p(max, 0)));
+        assert!(!ring.within(&p(0, max)));
+
+        // the cross products of this triangle overflow an i128
+        let triangle: LineString<i64> = vec![(min, min), (max, min), (min, max)].into();
+        assert!(triangle.within(&p(-1, -1)));
+        assert!(triangle.within(&p(-2, 0)));
+        // on the hypotenuse, a right edge
+        assert!(!triangle.within(&p(-1, 0)));
+        assert!(!triangle.within(&p(0, 0)));
+        assert!(!triangle.within(&p(max, max - 1)));
+
+        let unsigned: LineString<u64> =
+            vec![(0, 0), (u64::MAX, 0), (u64::MAX, u64::MAX), (0, u64::MAX)].into();
+        assert!(unsigned.within(&Coord {
+            x: u64::MAX - 1,
+            y: 1
+        }));
+        assert!(!unsigned.within(&Coord { x: u64::MAX, y: 1 }));
+    }
+}
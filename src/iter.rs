@@ -0,0 +1,65 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Iterator adaptors over point streams
+
+use geo_types::{Coord, CoordFloat};
+
+use crate::RayCasting;
+
+/// Extension trait for iterators of points
+pub trait RayCastingIteratorExt<T: CoordFloat, P: Into<Coord<T>>>:
+    Iterator<Item = P> + Sized
+{
+    /// Yields only the points within `geom`
+    fn filter_within<G: RayCasting<T, P>>(self, geom: &G) -> impl Iterator<Item = P> {
+        self.filter(move |pt| geom.within(pt))
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>>, I: Iterator<Item = P>> RayCastingIteratorExt<T, P> for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::RayCastingIteratorExt;
+
+    use geo_types::{Coord, LineString, Point};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        (x, y).into()
+    }
+
+    #[test]
+    fn filter_square() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        let pts = vec![
+            p(5.0, 5.0),
+            p(-10.0, 5.0),
+            p(5.0, 8.0),
+            p(15.0, 5.0),
+            p(8.0, 5.0),
+        ];
+        let inside = pts
+            .into_iter()
+            .filter_within(&poly_square)
+            .collect::<Vec<_>>();
+        assert_eq!(inside, vec![p(5.0, 5.0), p(5.0, 8.0), p(8.0, 5.0)]);
+
+        let count = vec![Point::new(5.0, 5.0), Point::new(-10.0, 5.0)]
+            .into_iter()
+            .filter_within(&poly_square)
+            .count();
+        assert_eq!(count, 1);
+    }
+}
@@ -6,65 +6,415 @@
 // copied, modified, or distributed except according to those terms.
 #![deny(warnings)]
 #![deny(missing_docs)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
 //! # geo-raycasting
 //!
 //! Ray Casting algorithm for the geo crate
+//!
+//! The `std` feature is enabled by default, disabling it makes the crate `no_std`, with float
+//! operations backed by `libm` and allocations by `alloc`.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use geo_types::{
+    Coord, CoordFloat, CoordNum, Geometry, GeometryCollection, Line, LineString, MultiPolygon,
+    Point, Polygon, Rect, Triangle,
+};
+
+mod bounds;
+mod builder;
+mod cache;
+mod centroid;
+mod contains;
+mod convex;
+mod diagnostics;
+mod distance;
+mod error;
+mod fixed;
+#[cfg(feature = "csv")]
+mod from_csv;
+#[cfg(feature = "geojson")]
+mod from_geojson;
+#[cfg(feature = "wkt")]
+mod from_wkt;
+mod geographic;
+mod holes;
+#[cfg(feature = "rstar")]
+mod index;
+mod int;
+mod iter;
+mod locate;
+mod measure;
+mod multi;
+mod orientation;
+mod prepared;
+mod raster;
+mod ray;
+mod ring;
+mod search;
+mod simplify;
+#[cfg(feature = "svg")]
+mod svg;
+#[cfg(test)]
+mod testutil;
+mod validation;
+mod winding;
 
-use geo_types::{Coord, CoordFloat, Line, LineString, Point, Polygon};
+pub use bounds::BoundingRect;
+pub use builder::PolygonBuilder;
+pub use cache::QueryCache;
+pub use centroid::Centroid;
+pub use contains::{Containment, Contains};
+pub use convex::{convex_hull, ConvexRayCasting, Convexity};
+pub use diagnostics::RayDiagnostics;
+pub use distance::BoundaryDistance;
+pub use error::RayCastError;
+pub use fixed::FixedPolygon;
+#[cfg(feature = "csv")]
+pub use from_csv::{polygon_from_csv, CsvError};
+#[cfg(feature = "geojson")]
+pub use from_geojson::from_geojson;
+#[cfg(feature = "wkt")]
+pub use from_wkt::{polygon_from_wkt, WktError};
+pub use geographic::{Densify, Geographic};
+pub use holes::Holes;
+#[cfg(feature = "rstar")]
+pub use index::PolygonIndex;
+pub use int::{IntCoord, RayCastingInt};
+pub use iter::RayCastingIteratorExt;
+pub use locate::{Locate, Location};
+pub use measure::Area;
+pub use multi::{Members, MultiRayCasting};
+pub use orientation::Orientation;
+pub use prepared::{PreparedMultiPolygon, PreparedPolygon};
+pub use raster::Rasterize;
+pub use ray::RobustRayCasting;
+pub use ring::{ring_from_points, Ring};
+pub use search::{find_container, locate_all_in, locate_in, nearest_polygon, partition_by_polygon};
+pub use simplify::Simplify;
+#[cfg(feature = "svg")]
+pub use svg::ToSvg;
+pub use validation::Validation;
+pub use winding::Winding;
 
 fn pt_in_polygon<T: CoordFloat>(pt: &Coord<T>, poly: &LineString<T>) -> bool {
-    let count = poly
-        .lines()
-        .filter(|line| ray_intersect_seg(pt, line))
-        .count();
+    match poly.bounding_rect() {
+        Some(rect) if !ray_misses_rect(pt, &rect) => pt_in_lines(pt, edges(poly)),
+        _ => false,
+    }
+}
+
+/// Nearest node of a square grid `grid` wide, along with its indices on both axes, or `None` when
+/// `grid` isn't strictly positive, which disables snapping
+fn snap<T: CoordFloat>(pt: &Coord<T>, grid: T) -> Option<(Coord<T>, T, T)> {
+    if grid > T::zero() {
+        let (x, y) = ((pt.x / grid).round(), (pt.y / grid).round());
+        Some((
+            Coord {
+                x: x * grid,
+                y: y * grid,
+            },
+            x,
+            y,
+        ))
+    } else {
+        None
+    }
+}
+
+/// Checks if a ring encloses no area, having fewer than 3 distinct points or only collinear ones
+fn is_degenerate<T: CoordFloat>(coords: &[Coord<T>]) -> bool {
+    let first = match coords.first() {
+        Some(first) => first,
+        None => return true,
+    };
+    match coords.iter().find(|coord| *coord != first) {
+        Some(second) => coords
+            .iter()
+            .all(|coord| cross(first, second, coord) == T::zero()),
+        None => true,
+    }
+}
+
+/// Segments of a ring, including the closing one when the ring isn't explicitly closed
+fn ring_lines<T: CoordNum>(ring: &LineString<T>) -> impl Iterator<Item = Line<T>> + '_ {
+    let closing = match (ring.0.first(), ring.0.last()) {
+        (Some(first), Some(last)) if first != last => Some(Line::new(*last, *first)),
+        _ => None,
+    };
+    ring.lines().chain(closing)
+}
+
+/// Segments of a ring that can be crossed, skipping the zero-length ones left by repeated
+/// consecutive vertices
+///
+/// A zero-length segment is never crossed anyway, since its half-open Y range is empty, skipping
+/// it just makes that explicit and saves the test.
+fn edges<T: CoordNum>(ring: &LineString<T>) -> impl Iterator<Item = Line<T>> + '_ {
+    ring_lines(ring).filter(|line| line.start != line.end)
+}
+
+fn pt_in_lines<T: CoordFloat, I: IntoIterator<Item = Line<T>>>(pt: &Coord<T>, lines: I) -> bool {
+    count_crossings(pt, lines) % 2 == 1
+}
+
+fn count_crossings<T: CoordFloat, I: IntoIterator<Item = Line<T>>>(
+    pt: &Coord<T>,
+    lines: I,
+) -> usize {
+    lines
+        .into_iter()
+        .filter(|line| ray_intersects_segment(pt, line))
+        .count()
+}
 
-    count % 2 == 1
+fn pt_in_rect<T: CoordFloat>(pt: &Coord<T>, rect: &Rect<T>) -> bool {
+    let (min, max) = (rect.min(), rect.max());
+    min.x <= pt.x && pt.x < max.x && min.y <= pt.y && pt.y < max.y
 }
 
-fn ray_intersect_seg<T: CoordFloat>(p: &Coord<T>, line: &Line<T>) -> bool {
-    let (pt_x, mut pt_y) = p.x_y();
-    let (a, b) = if line.start.y > line.end.y {
-        (&line.end, &line.start)
+/// Checks if a ray cast from `origin` crosses `segment`
+///
+/// The ray starts at `origin` and runs horizontally towards positive X, parallel to the X axis.
+/// Segments are half-open on the Y axis: the lower endpoint is included and the upper one isn't,
+/// so horizontal segments are never crossed, and a vertex at the same height as the origin is
+/// counted exactly once by the two segments sharing it. An origin lying on the segment itself
+/// isn't a crossing, the segment must be strictly right of it.
+///
+/// Applied to a ring, this makes points on left and bottom edges inside and points on right and
+/// top edges outside, see `RayCasting`. There is no tolerance involved, the side of the segment
+/// is the sign of a cross product, so the results for `f32` and `f64` on the same geometry only
+/// differ when coordinates round differently. The cross product can still round to the wrong
+/// sign for points extremely close to a segment, enabling the `robust` feature computes it with
+/// exact arithmetic instead.
+///
+/// This is the primitive `RayCasting::within` is built upon: a point is within a ring when the
+/// ray crosses an odd number of its segments.
+pub fn ray_intersects_segment<T: CoordFloat>(origin: &Coord<T>, segment: &Line<T>) -> bool {
+    let (a, b) = if segment.start.y > segment.end.y {
+        (&segment.end, &segment.start)
     } else {
-        (&line.start, &line.end)
+        (&segment.start, &segment.end)
+    };
+    a.y <= origin.y && origin.y < b.y && is_left(a, b, origin)
+}
+
+/// Checks if `c` lies strictly left of `a -> b`
+#[cfg(not(feature = "robust"))]
+fn is_left<T: CoordFloat>(a: &Coord<T>, b: &Coord<T>, c: &Coord<T>) -> bool {
+    cross(a, b, c) > T::zero()
+}
+
+/// Checks if `c` lies strictly left of `a -> b`, with an exact predicate
+#[cfg(feature = "robust")]
+fn is_left<T: CoordFloat>(a: &Coord<T>, b: &Coord<T>, c: &Coord<T>) -> bool {
+    let exact = |coord: &Coord<T>| robust::Coord {
+        x: coord.x.to_f64().unwrap_or(f64::NAN),
+        y: coord.y.to_f64().unwrap_or(f64::NAN),
     };
+    robust::orient2d(exact(a), exact(b), exact(c)) > 0.
+}
+
+/// A ray cast from a point outside `rect`, or on its right or top edges, can't cross an odd
+/// number of the segments within it
+fn ray_misses_rect<T: CoordFloat>(pt: &Coord<T>, rect: &Rect<T>) -> bool {
+    let (min, max) = (rect.min(), rect.max());
+    pt.x < min.x || pt.x >= max.x || pt.y < min.y || pt.y >= max.y
+}
 
-    if pt_y == a.y || pt_y == b.y {
-        pt_y = pt_y + T::min_positive_value();
+fn bounding_rect<'a, T: CoordFloat + 'a, I: IntoIterator<Item = &'a Coord<T>>>(
+    coords: I,
+) -> Option<Rect<T>> {
+    let mut coords = coords.into_iter();
+    let first = *coords.next()?;
+    let (min, max) = coords.fold((first, first), |(min, max), c| {
+        (
+            Coord {
+                x: min.x.min(c.x),
+                y: min.y.min(c.y),
+            },
+            Coord {
+                x: max.x.max(c.x),
+                y: max.y.max(c.y),
+            },
+        )
+    });
+    Some(Rect::new(min, max))
+}
+
+fn closest_point_on_segment<T: CoordFloat>(pt: &Coord<T>, line: &Line<T>) -> Coord<T> {
+    let delta = line.delta();
+    let len2 = delta.x * delta.x + delta.y * delta.y;
+    if len2 == T::zero() {
+        return line.start;
     }
 
-    if (pt_y > b.y || pt_y < a.y) || pt_x > a.x.max(b.x) {
-        false
-    } else if pt_x < a.x.min(b.x) {
-        true
-    } else {
-        let m_red = if (a.x - b.x).abs() > T::min_positive_value() {
-            (b.y - a.y) / (b.x - a.x)
-        } else {
-            T::max_value()
-        };
-        let m_blue = if (a.x - pt_x).abs() > T::min_positive_value() {
-            (pt_y - a.y) / (pt_x - a.x)
-        } else {
-            T::max_value()
-        };
-        m_blue >= m_red
+    let t = ((pt.x - line.start.x) * delta.x + (pt.y - line.start.y) * delta.y) / len2;
+    let t = t.max(T::zero()).min(T::one());
+    Coord {
+        x: line.start.x + delta.x * t,
+        y: line.start.y + delta.y * t,
     }
 }
 
+fn segment_distance<T: CoordFloat>(pt: &Coord<T>, line: &Line<T>) -> T {
+    let closest = closest_point_on_segment(pt, line);
+    (pt.x - closest.x).hypot(pt.y - closest.y)
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `c`, positive when counter-clockwise
+fn cross<T: CoordFloat>(a: &Coord<T>, b: &Coord<T>, c: &Coord<T>) -> T {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Checks if two segments share at least one point, touching counts
+fn segments_intersect<T: CoordFloat>(first: &Line<T>, second: &Line<T>) -> bool {
+    fn on_segment<T: CoordFloat>(pt: &Coord<T>, line: &Line<T>) -> bool {
+        pt.x >= line.start.x.min(line.end.x)
+            && pt.x <= line.start.x.max(line.end.x)
+            && pt.y >= line.start.y.min(line.end.y)
+            && pt.y <= line.start.y.max(line.end.y)
+    }
+
+    let zero = T::zero();
+    let d1 = cross(&first.start, &first.end, &second.start);
+    let d2 = cross(&first.start, &first.end, &second.end);
+    let d3 = cross(&second.start, &second.end, &first.start);
+    let d4 = cross(&second.start, &second.end, &first.end);
+
+    if ((d1 > zero && d2 < zero) || (d1 < zero && d2 > zero))
+        && ((d3 > zero && d4 < zero) || (d3 < zero && d4 > zero))
+    {
+        return true;
+    }
+
+    (d1 == zero && on_segment(&second.start, first))
+        || (d2 == zero && on_segment(&second.end, first))
+        || (d3 == zero && on_segment(&first.start, second))
+        || (d4 == zero && on_segment(&first.end, second))
+}
+
 /// Trait implementing Ray Casting algorith
+///
+/// Points on the boundary follow a half-open convention: left and bottom edges are inside, right
+/// and top edges are outside. More precisely, a boundary point is inside when the area extends
+/// right of it, or straight above it along a horizontal edge. This is applied the same way to
+/// every ring, holes included, so two areas sharing an edge never both contain a point on it.
+///
+/// Vertices need no special case: the segments meeting at a vertex are never crossed by a ray
+/// cast from it, since it doesn't lie strictly left of them, so the result only depends on the
+/// other segments and follows the same rule.
 pub trait RayCasting<T: CoordFloat, P: Into<Coord<T>>> {
     /// Checks if a point is within a polygonal area
     fn within(&self, pt: &P) -> bool;
+
+    /// Checks a batch of points, the result is aligned index-for-index with `pts`
+    fn within_many(&self, pts: &[P]) -> Vec<bool> {
+        pts.iter().map(|pt| self.within(pt)).collect()
+    }
+
+    /// Checks a batch of points, calling `f` with each point's index and result, in order,
+    /// without allocating
+    fn for_each_within<F: FnMut(usize, bool)>(&self, pts: &[P], mut f: F)
+    where
+        Self: Sized,
+    {
+        for (index, pt) in pts.iter().enumerate() {
+            f(index, self.within(pt));
+        }
+    }
+
+    /// Counts the points within the area, consuming any iterator of points
+    fn count_within<I: IntoIterator<Item = P>>(&self, pts: I) -> usize
+    where
+        Self: Sized,
+    {
+        pts.into_iter().filter(|pt| self.within(pt)).count()
+    }
+
+    /// Checks a batch of points, packing the results one bit per point
+    ///
+    /// The result of `pts[i]` is bit `i % 64` of word `i / 64`, counting from the least significant
+    /// bit, so it can be read back with `words[i / 64] >> (i % 64) & 1 == 1`. The unused high bits
+    /// of the last word are zero.
+    fn within_bitset(&self, pts: &[P]) -> Vec<u64> {
+        let mut words = alloc::vec![0; pts.len().div_ceil(64)];
+        for (index, pt) in pts.iter().enumerate() {
+            if self.within(pt) {
+                words[index / 64] |= 1 << (index % 64);
+            }
+        }
+        words
+    }
+
+    /// Checks if a point is within the area, after snapping it to the nearest node of a square
+    /// grid `grid` wide
+    ///
+    /// Points snapping to the same node get the same result, so float noise in the query can't
+    /// flip it, at the price of checking the node instead of the point itself: the result is exact
+    /// only for points lying on the grid. A `grid` that isn't strictly positive disables snapping.
+    fn within_snapped(&self, pt: &P, grid: T) -> bool
+    where
+        P: Copy,
+        Self: RayCasting<T, Coord<T>> + Sized,
+    {
+        let coord = (*pt).into();
+        let snapped = snap(&coord, grid).map_or(coord, |(snapped, _, _)| snapped);
+        RayCasting::<T, Coord<T>>::within(self, &snapped)
+    }
+
+    /// Checks a batch of points in parallel, the result is aligned index-for-index with `pts`
+    #[cfg(feature = "rayon")]
+    fn within_par(&self, pts: &[P]) -> Vec<bool>
+    where
+        Self: Sync,
+        P: Sync,
+    {
+        use rayon::prelude::*;
+
+        pts.par_iter().map(|pt| self.within(pt)).collect()
+    }
 }
 
+/// Lets generic code take geometries by reference, trait objects included
+///
+/// ```
+/// use geo_raycasting::RayCasting;
+/// use geo_types::{Coord, LineString, Polygon};
+///
+/// fn count_inside<G: RayCasting<f64, Coord<f64>>>(geom: G, pts: &[Coord<f64>]) -> usize {
+///     pts.iter().filter(|pt| geom.within(pt)).count()
+/// }
+///
+/// let square: LineString<f64> = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+/// let poly = Polygon::new(square, vec![]);
+/// let pts = [(5.0, 5.0).into(), (15.0, 5.0).into()];
+///
+/// assert_eq!(count_inside(&poly, &pts), 1);
+/// let geom: &dyn RayCasting<f64, Coord<f64>> = &poly;
+/// assert_eq!(count_inside(geom, &pts), 1);
+/// assert_eq!(count_inside(poly, &pts), 1);
+/// ```
+impl<T: CoordFloat, P: Into<Coord<T>>, G: RayCasting<T, P> + ?Sized> RayCasting<T, P> for &G {
+    fn within(&self, pt: &P) -> bool {
+        (**self).within(pt)
+    }
+}
+
+/// Rings that aren't explicitly closed are closed virtually, joining the last coordinate back
+/// to the first one. Degenerate rings, with fewer than 3 distinct points or only collinear ones,
+/// contain no point, see `Validation::is_valid`: with the half-open convention a ray crosses their
+/// segments an even number of times, so there's no need to check for them on every query.
 impl<T: CoordFloat> RayCasting<T, Point<T>> for LineString<T> {
     fn within(&self, pt: &Point<T>) -> bool {
-        pt_in_polygon(&pt.x_y().into(), self)
+        pt_in_polygon(&pt.0, self)
     }
 }
 
+/// See the `Point` implementation for ring closure and degenerate rings.
 impl<T: CoordFloat> RayCasting<T, Coord<T>> for LineString<T> {
     fn within(&self, pt: &Coord<T>) -> bool {
         pt_in_polygon(pt, self)
@@ -73,12 +423,7 @@ impl<T: CoordFloat> RayCasting<T, Coord<T>> for LineString<T> {
 
 impl<T: CoordFloat> RayCasting<T, Point<T>> for Polygon<T> {
     fn within(&self, pt: &Point<T>) -> bool {
-        let coord = pt.x_y().into();
-        pt_in_polygon(&coord, self.exterior())
-            && !self
-                .interiors()
-                .iter()
-                .any(|line| pt_in_polygon(&coord, line))
+        self.within(&pt.0)
     }
 }
 
@@ -89,16 +434,321 @@ impl<T: CoordFloat> RayCasting<T, Coord<T>> for Polygon<T> {
     }
 }
 
+/// Bare `(x, y)` tuples are accepted as well, without wrapping them in a `Coord` or `Point`.
+///
+/// ```
+/// use geo_raycasting::RayCasting;
+/// use geo_types::{LineString, Polygon};
+///
+/// let square: LineString<f64> = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+/// assert!(square.within(&(5.0, 5.0)));
+///
+/// let poly = Polygon::new(square, vec![]);
+/// assert!(!poly.within(&(15.0, 5.0)));
+/// ```
+impl<T: CoordFloat> RayCasting<T, (T, T)> for LineString<T> {
+    fn within(&self, pt: &(T, T)) -> bool {
+        pt_in_polygon(&Coord::from(*pt), self)
+    }
+}
+
+/// See the `LineString` implementation.
+impl<T: CoordFloat> RayCasting<T, (T, T)> for Polygon<T> {
+    fn within(&self, pt: &(T, T)) -> bool {
+        self.within(&Coord::from(*pt))
+    }
+}
+
+impl<T: CoordFloat> RayCasting<T, Point<T>> for MultiPolygon<T> {
+    fn within(&self, pt: &Point<T>) -> bool {
+        self.iter().any(|poly| poly.within(pt))
+    }
+}
+
+impl<T: CoordFloat> RayCasting<T, Coord<T>> for MultiPolygon<T> {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        self.iter().any(|poly| poly.within(pt))
+    }
+}
+
+/// Segments are taken as the edges of one or more rings, in any order and direction, with no
+/// closing segment added. The crossings are counted exactly like for a `LineString`.
+impl<T: CoordFloat> RayCasting<T, Point<T>> for [Line<T>] {
+    fn within(&self, pt: &Point<T>) -> bool {
+        pt_in_lines(&pt.0, self.iter().copied())
+    }
+}
+
+/// See the `Point` implementation for how segments are read.
+impl<T: CoordFloat> RayCasting<T, Coord<T>> for [Line<T>] {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        pt_in_lines(pt, self.iter().copied())
+    }
+}
+
+impl<T: CoordFloat> RayCasting<T, Point<T>> for Triangle<T> {
+    fn within(&self, pt: &Point<T>) -> bool {
+        pt_in_lines(&pt.0, self.to_lines())
+    }
+}
+
+impl<T: CoordFloat> RayCasting<T, Coord<T>> for Triangle<T> {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        pt_in_lines(pt, self.to_lines())
+    }
+}
+
+/// A segment has no area, so a point is only within it when it lies on the segment, within
+/// `T::epsilon()` of it. No ray is cast, and both endpoints are included.
+impl<T: CoordFloat> RayCasting<T, Point<T>> for Line<T> {
+    fn within(&self, pt: &Point<T>) -> bool {
+        self.within(&pt.0)
+    }
+}
+
+/// See the `Point` implementation for the tolerance.
+impl<T: CoordFloat> RayCasting<T, Coord<T>> for Line<T> {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        segment_distance(pt, self) <= T::epsilon()
+    }
+}
+
+/// Rectangles are tested with plain comparisons, no ray is cast.
+///
+/// Edges follow the same convention as rings, with the minimum corner inside and the maximum one
+/// outside, so a `Rect` bounding a polygon never rejects a point the polygon itself contains,
+/// making it a safe pre-filter.
+impl<T: CoordFloat> RayCasting<T, Point<T>> for Rect<T> {
+    fn within(&self, pt: &Point<T>) -> bool {
+        pt_in_rect(&pt.0, self)
+    }
+}
+
+/// See the `Point` implementation for the edge convention.
+impl<T: CoordFloat> RayCasting<T, Coord<T>> for Rect<T> {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        pt_in_rect(pt, self)
+    }
+}
+
+/// Dispatches on the geometry variant:
+///
+/// * `Polygon`, `MultiPolygon`, `Rect` and `Triangle` run their own containment test
+/// * `LineString` is treated as a ring only when closed, otherwise it has no area
+/// * `Line` contains only the points lying on it
+/// * `GeometryCollection` contains a point when any of its members does
+/// * `Point`, `MultiPoint` and `MultiLineString` have no area and never contain a point
+impl<T: CoordFloat> RayCasting<T, Point<T>> for Geometry<T> {
+    fn within(&self, pt: &Point<T>) -> bool {
+        self.within(&pt.0)
+    }
+}
+
+/// See the `Point` implementation for the per-variant behavior.
+impl<T: CoordFloat> RayCasting<T, Coord<T>> for Geometry<T> {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        match self {
+            Geometry::Polygon(poly) => poly.within(pt),
+            Geometry::MultiPolygon(multi) => multi.within(pt),
+            Geometry::Rect(rect) => rect.within(pt),
+            Geometry::Triangle(triangle) => triangle.within(pt),
+            Geometry::Line(line) => line.within(pt),
+            Geometry::LineString(line) => line.is_closed() && line.within(pt),
+            Geometry::GeometryCollection(collection) => collection.within(pt),
+            Geometry::Point(_) | Geometry::MultiPoint(_) | Geometry::MultiLineString(_) => false,
+        }
+    }
+}
+
+impl<T: CoordFloat> RayCasting<T, Point<T>> for GeometryCollection<T> {
+    fn within(&self, pt: &Point<T>) -> bool {
+        self.iter().any(|geom| geom.within(pt))
+    }
+}
+
+impl<T: CoordFloat> RayCasting<T, Coord<T>> for GeometryCollection<T> {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        self.iter().any(|geom| geom.within(pt))
+    }
+}
+
+/// Trait implementing Ray Casting algorithm with input validation
+///
+/// Comparisons against NaN are always false, so `RayCasting::within` silently returns a
+/// meaningless result when the query point or the geometry holds a NaN or infinite coordinate.
+/// `try_within` reports those cases as `RayCastError::NonFiniteCoordinate` instead.
+///
+/// Likewise an exterior ring with no coordinates is reported as `RayCastError::EmptyRing`, and
+/// one enclosing no area as `RayCastError::DegeneratePolygon`, where `within` would just return
+/// `false`. Degenerate holes are accepted, they remove no point from the area.
+pub trait TryRayCasting<T: CoordFloat, P: Into<Coord<T>>> {
+    /// Checks if a point is within a polygonal area, failing on non-finite coordinates and
+    /// degenerate exteriors
+    fn try_within(&self, pt: &P) -> Result<bool, RayCastError>;
+}
+
+fn check_finite<'a, T: CoordFloat + 'a, I: IntoIterator<Item = &'a Coord<T>>>(
+    coords: I,
+) -> Result<(), RayCastError> {
+    if coords
+        .into_iter()
+        .all(|c| c.x.is_finite() && c.y.is_finite())
+    {
+        Ok(())
+    } else {
+        Err(RayCastError::NonFiniteCoordinate)
+    }
+}
+
+fn check_exterior<T: CoordFloat>(ring: &LineString<T>) -> Result<(), RayCastError> {
+    if ring.0.is_empty() {
+        Err(RayCastError::EmptyRing)
+    } else if is_degenerate(&ring.0) {
+        Err(RayCastError::DegeneratePolygon)
+    } else {
+        Ok(())
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> TryRayCasting<T, P> for LineString<T> {
+    fn try_within(&self, pt: &P) -> Result<bool, RayCastError> {
+        let coord = (*pt).into();
+        check_finite(core::iter::once(&coord).chain(self.coords()))?;
+        check_exterior(self)?;
+        Ok(self.within(&coord))
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> TryRayCasting<T, P> for Polygon<T> {
+    fn try_within(&self, pt: &P) -> Result<bool, RayCastError> {
+        let coord = (*pt).into();
+        check_finite(
+            core::iter::once(&coord)
+                .chain(self.exterior().coords())
+                .chain(self.interiors().iter().flat_map(|ring| ring.coords())),
+        )?;
+        check_exterior(self.exterior())?;
+        Ok(self.within(&coord))
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> TryRayCasting<T, P> for MultiPolygon<T> {
+    fn try_within(&self, pt: &P) -> Result<bool, RayCastError> {
+        let coord = (*pt).into();
+        check_finite(core::iter::once(&coord).chain(self.iter().flat_map(|poly| {
+            poly.exterior()
+                .coords()
+                .chain(poly.interiors().iter().flat_map(|ring| ring.coords()))
+        })))?;
+        self.iter()
+            .try_for_each(|poly| check_exterior(poly.exterior()))?;
+        Ok(self.within(&coord))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::RayCasting;
+    use super::{
+        pt_in_lines, pt_in_polygon, ray_intersects_segment, ring_lines, RayCastError, RayCasting,
+        TryRayCasting,
+    };
+
+    use crate::testutil::{assert_agree, grid};
 
-    use geo_types::{Coord, LineString, Point, Polygon};
+    use geo_types::{
+        Coord, Geometry, GeometryCollection, Line, LineString, MultiPolygon, Point, Polygon, Rect,
+        Triangle,
+    };
 
     fn p(x: f64, y: f64) -> Coord<f64> {
         (x, y).into()
     }
 
+    #[test]
+    fn ray_segment() {
+        let vertical = Line::new(p(10.0, 0.0), p(10.0, 10.0));
+        assert!(ray_intersects_segment(&p(5.0, 5.0), &vertical));
+        assert!(!ray_intersects_segment(&p(10.0, 5.0), &vertical));
+        assert!(!ray_intersects_segment(&p(15.0, 5.0), &vertical));
+        assert!(!ray_intersects_segment(&p(5.0, 15.0), &vertical));
+        assert!(ray_intersects_segment(&p(5.0, 0.0), &vertical));
+        assert!(!ray_intersects_segment(&p(5.0, 10.0), &vertical));
+        let reversed = Line::new(vertical.end, vertical.start);
+        assert!(ray_intersects_segment(&p(5.0, 0.0), &reversed));
+        assert!(!ray_intersects_segment(&p(5.0, 10.0), &reversed));
+
+        let diagonal = Line::new(p(0.0, 0.0), p(10.0, 10.0));
+        assert!(ray_intersects_segment(&p(2.0, 5.0), &diagonal));
+        assert!(!ray_intersects_segment(&p(8.0, 5.0), &diagonal));
+
+        let horizontal = Line::new(p(0.0, 5.0), p(10.0, 5.0));
+        assert!(!ray_intersects_segment(&p(5.0, 5.0), &horizontal));
+        assert!(!ray_intersects_segment(&p(-5.0, 5.0), &horizontal));
+    }
+
+    #[cfg(feature = "robust")]
+    #[test]
+    fn ray_segment_robust() {
+        // with plain floats the cross product of this point rounds to zero, even though it lies
+        // just left of the diagonal
+        let diagonal = Line::new(p(24.3, 24.3), p(-12.1, -12.1));
+        let pt = p(0.5, 0.500_000_000_000_000_2);
+        assert!(ray_intersects_segment(&pt, &diagonal));
+
+        let triangle: LineString<f64> = vec![(-12.1, -12.1), (60.0, -12.1), (24.3, 24.3)].into();
+        assert!(!triangle.within(&pt));
+        assert!(triangle.within(&p(0.5, 0.499_999_999_999_999_9)));
+    }
+
+    #[test]
+    fn bounding_rect_rejection() {
+        let rings: Vec<LineString<f64>> = vec![
+            vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ]
+            .into(),
+            vec![(2.5, 2.5), (7.5, 2.5), (7.5, 7.5), (2.5, 7.5), (2.5, 2.5)].into(),
+            vec![
+                (0.0, 0.0),
+                (2.5, 2.5),
+                (0.0, 10.0),
+                (2.5, 7.5),
+                (7.5, 7.5),
+                (10.0, 10.0),
+                (10.0, 0.0),
+                (2.5, 2.5),
+            ]
+            .into(),
+            vec![
+                (3.0, 0.0),
+                (7.0, 0.0),
+                (10.0, 5.0),
+                (7.0, 10.0),
+                (3.0, 10.0),
+                (0.0, 5.0),
+                (3.0, 0.0),
+            ]
+            .into(),
+        ];
+        for ring in &rings {
+            for x in -8..=28 {
+                for y in -8..=28 {
+                    let pt = p(f64::from(x) / 2.0, f64::from(y) / 2.0);
+                    assert_eq!(
+                        pt_in_polygon(&pt, ring),
+                        pt_in_lines(&pt, ring_lines(ring)),
+                        "{:?}",
+                        pt
+                    );
+                }
+            }
+        }
+    }
+
     #[test]
     fn poly_square() {
         let poly_square: LineString<f64> = vec![
@@ -112,9 +762,292 @@ mod tests {
         assert!(poly_square.within(&p(5.0, 5.0)));
         assert!(poly_square.within(&p(5.0, 8.0)));
         assert!(!poly_square.within(&p(-10.0, 5.0)));
-        assert!(!poly_square.within(&p(0.0, 5.0)));
+        assert!(!poly_square.within(&p(-10.0, 10.0)));
+        assert!(poly_square.within(&p(0.0, 5.0)));
         assert!(poly_square.within(&p(8.0, 5.0)));
         assert!(!poly_square.within(&p(10.0, 10.0)));
+        let poly = Polygon::new(poly_square, vec![]);
+        assert_agree(&poly, &grid(&poly, 0.5));
+    }
+
+    #[test]
+    fn poly_square_repeated_vertex() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert_eq!(super::edges(&poly_square).count(), 4);
+        assert!(poly_square.within(&p(5.0, 5.0)));
+        assert!(poly_square.within(&p(5.0, 0.0)));
+        assert!(poly_square.within(&p(0.0, 5.0)));
+        assert!(!poly_square.within(&p(10.0, 5.0)));
+        assert!(!poly_square.within(&p(-10.0, 0.0)));
+        assert!(!poly_square.within(&p(-10.0, 10.0)));
+        assert!(!poly_square.within(&p(15.0, 5.0)));
+    }
+
+    #[test]
+    fn poly_square_edges() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        let mut reversed = poly_square.clone();
+        reversed.0.reverse();
+        let poly = Polygon::new(poly_square.clone(), vec![]);
+        for ring in [&poly_square, &reversed] {
+            // left and bottom edges are inside
+            assert!(ring.within(&p(0.0, 5.0)));
+            assert!(ring.within(&p(5.0, 0.0)));
+            assert!(ring.within(&p(0.0, 0.0)));
+            // right and top edges are outside
+            assert!(!ring.within(&p(10.0, 5.0)));
+            assert!(!ring.within(&p(5.0, 10.0)));
+            assert!(!ring.within(&p(10.0, 0.0)));
+            assert!(!ring.within(&p(0.0, 10.0)));
+            assert!(!ring.within(&p(10.0, 10.0)));
+        }
+        assert!(poly.within(&p(0.0, 5.0)));
+        assert!(poly.within(&p(5.0, 0.0)));
+        assert!(!poly.within(&p(10.0, 5.0)));
+        assert!(!poly.within(&p(5.0, 10.0)));
+
+        // two squares sharing an edge, any point on it belongs to exactly one of them
+        let right: LineString<f64> =
+            vec![(10.0, 0.0), (20.0, 0.0), (20.0, 10.0), (10.0, 10.0)].into();
+        for y in 0..=10 {
+            let pt = p(10.0, f64::from(y));
+            assert!(!poly_square.within(&pt));
+            assert_eq!(right.within(&pt), y < 10);
+        }
+    }
+
+    #[test]
+    fn poly_square_open() {
+        let poly_square_open: LineString<f64> =
+            vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+        assert!(poly_square_open.within(&p(5.0, 5.0)));
+        assert!(poly_square_open.within(&p(5.0, 8.0)));
+        assert!(!poly_square_open.within(&p(-10.0, 5.0)));
+        assert!(!poly_square_open.within(&p(15.0, 5.0)));
+    }
+
+    #[test]
+    fn poly_square_nan() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert!(matches!(
+            poly_square.try_within(&Coord {
+                x: f64::NAN,
+                y: 5.0
+            }),
+            Err(RayCastError::NonFiniteCoordinate)
+        ));
+        assert!(matches!(
+            poly_square.try_within(&p(f64::INFINITY, 5.0)),
+            Err(RayCastError::NonFiniteCoordinate)
+        ));
+        assert!(matches!(poly_square.try_within(&p(5.0, 5.0)), Ok(true)));
+        assert!(matches!(
+            poly_square.try_within(&Point::new(-10.0, 5.0)),
+            Ok(false)
+        ));
+
+        let poly_nan = Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (10.0, f64::NAN), (0.0, 10.0), (0.0, 0.0)]),
+            vec![],
+        );
+        assert!(matches!(
+            poly_nan.try_within(&p(1.0, 1.0)),
+            Err(RayCastError::NonFiniteCoordinate)
+        ));
+        assert!(matches!(
+            MultiPolygon(vec![poly_nan]).try_within(&p(1.0, 1.0)),
+            Err(RayCastError::NonFiniteCoordinate)
+        ));
+    }
+
+    #[test]
+    fn try_within_errors() {
+        let square: LineString<f64> =
+            vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+        let cases: Vec<(LineString<f64>, Coord<f64>, RayCastError)> = vec![
+            (
+                square.clone(),
+                p(f64::NAN, 5.0),
+                RayCastError::NonFiniteCoordinate,
+            ),
+            (LineString(vec![]), p(5.0, 5.0), RayCastError::EmptyRing),
+            (
+                vec![(0.0, 0.0), (5.0, 5.0), (10.0, 10.0)].into(),
+                p(5.0, 5.0),
+                RayCastError::DegeneratePolygon,
+            ),
+            (
+                vec![(1.0, 1.0), (1.0, 1.0)].into(),
+                p(1.0, 1.0),
+                RayCastError::DegeneratePolygon,
+            ),
+        ];
+        for (ring, pt, error) in cases {
+            let error = error.to_string();
+            assert_eq!(ring.try_within(&pt).unwrap_err().to_string(), error);
+            let poly = Polygon::new(ring, vec![]);
+            assert_eq!(poly.try_within(&pt).unwrap_err().to_string(), error);
+            let multi = MultiPolygon(vec![poly]);
+            assert_eq!(multi.try_within(&pt).unwrap_err().to_string(), error);
+        }
+
+        // degenerate holes remove no point, they are accepted
+        let poly = Polygon::new(square, vec![LineString(vec![])]);
+        assert!(matches!(poly.try_within(&p(5.0, 5.0)), Ok(true)));
+        assert!(matches!(
+            MultiPolygon::<f64>(vec![]).try_within(&p(5.0, 5.0)),
+            Ok(false)
+        ));
+        assert_eq!(
+            RayCastError::DegeneratePolygon.to_string(),
+            "polygon encloses no area"
+        );
+    }
+
+    #[test]
+    fn poly_square_many() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        let pts = [
+            p(5.0, 5.0),
+            p(-10.0, 5.0),
+            p(5.0, 8.0),
+            p(0.0, 5.0),
+            p(8.0, 5.0),
+            p(10.0, 10.0),
+        ];
+        assert_eq!(
+            poly_square.within_many(&pts),
+            vec![true, false, true, true, true, false]
+        );
+        assert!(poly_square.within_many(&[] as &[Coord<f64>]).is_empty());
+    }
+
+    #[test]
+    fn poly_square_for_each() {
+        let poly_square: LineString<f64> =
+            vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+        let pts = (-5..15)
+            .flat_map(|x| (-5..15).map(move |y| p(f64::from(x), f64::from(y))))
+            .collect::<Vec<_>>();
+        let mut count = 0;
+        let mut indices = Vec::new();
+        poly_square.for_each_within(&pts, |index, within| {
+            indices.push(index);
+            if within {
+                count += 1;
+            }
+        });
+        let expected = poly_square.within_many(&pts);
+        assert_eq!(count, expected.iter().filter(|within| **within).count());
+        assert_eq!(count, 100);
+        assert_eq!(indices, (0..pts.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn poly_square_count() {
+        let poly_square: LineString<f64> =
+            vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+        let diagonal = (0..100).map(|i| p(f64::from(i) * 0.2 - 5.0, f64::from(i) * 0.2 - 5.0));
+        assert_eq!(poly_square.count_within(diagonal), 50);
+        let pts = [p(5.0, 5.0), p(15.0, 5.0), p(5.0, 8.0)];
+        assert_eq!(poly_square.count_within(pts), 2);
+        assert_eq!(poly_square.count_within(Vec::<Coord<f64>>::new()), 0);
+    }
+
+    #[test]
+    fn poly_square_bitset() {
+        let poly_square: LineString<f64> =
+            vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+        let pts = (-5..15)
+            .flat_map(|x| (-5..15).map(move |y| p(f64::from(x), f64::from(y) + 0.5)))
+            .collect::<Vec<_>>();
+        let words = poly_square.within_bitset(&pts);
+        assert_eq!(words.len(), 7);
+        for (i, pt) in pts.iter().enumerate() {
+            assert_eq!(words[i / 64] >> (i % 64) & 1 == 1, poly_square.within(pt));
+        }
+        assert_eq!(words[6] >> (pts.len() % 64), 0);
+        assert_eq!(words.iter().map(|word| word.count_ones()).sum::<u32>(), 100);
+        assert!(poly_square.within_bitset(&[] as &[Coord<f64>]).is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn poly_square_par() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        let pts = (0..1000)
+            .map(|i| p(f64::from(i % 40) / 2.0 - 5.0, f64::from(i / 40) - 5.0))
+            .collect::<Vec<_>>();
+        assert_eq!(poly_square.within_par(&pts), poly_square.within_many(&pts));
+    }
+
+    #[test]
+    fn poly_square_snapped() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        // on both sides of the right edge, the plain test disagrees
+        let (before, after) = (p(10.0 - 1e-9, 5.0), p(10.0 + 1e-9, 5.0));
+        assert!(poly_square.within(&before));
+        assert!(!poly_square.within(&after));
+        assert_eq!(
+            poly_square.within_snapped(&before, 0.5),
+            poly_square.within_snapped(&after, 0.5)
+        );
+        assert!(!poly_square.within_snapped(&before, 0.5));
+
+        for dx in 0..10 {
+            let nudge = f64::from(dx) * 0.02;
+            assert!(poly_square.within_snapped(&p(0.1 - nudge, 5.0), 0.5));
+            assert!(poly_square.within_snapped(&Point::new(5.0 + nudge, 5.0), 0.5));
+            assert!(!poly_square.within_snapped(&(-0.6 + nudge, 5.0), 0.5));
+        }
+        let poly = Polygon::new(poly_square, vec![]);
+        assert!(poly.within_snapped(&p(5.1, 5.1), 1.0));
+        assert!(poly.within_snapped(&before, 0.0));
+        assert!(!poly.within_snapped(&p(-0.4, 5.0), 0.0));
     }
 
     #[test]
@@ -138,10 +1071,66 @@ mod tests {
         assert!(!poly_square_hole.within(&p(5.0, 5.0)));
         assert!(poly_square_hole.within(&p(5.0, 8.0)));
         assert!(!poly_square_hole.within(&p(-10.0, 5.0)));
-        assert!(!poly_square_hole.within(&p(0.0, 5.0)));
-        assert!(poly_square_hole.within(&p(10.0, 5.0)));
+        assert!(poly_square_hole.within(&p(0.0, 5.0)));
+        assert!(!poly_square_hole.within(&p(10.0, 5.0)));
         assert!(poly_square_hole.within(&p(8.0, 5.0)));
+        // the hole follows the same convention, so its left edge is outside the polygon
+        assert!(!poly_square_hole.within(&p(2.5, 5.0)));
+        assert!(poly_square_hole.within(&p(7.5, 5.0)));
         assert!(!poly_square_hole.within(&p(10.0, 10.0)));
+        assert_agree(&poly_square_hole, &grid(&poly_square_hole, 0.5));
+    }
+
+    #[test]
+    fn tuple_matches_coord() {
+        let poly_square_hole = Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+            ])],
+        );
+        for pt in [
+            (5.0, 5.0),
+            (5.0, 8.0),
+            (0.0, 5.0),
+            (10.0, 5.0),
+            (-10.0, 5.0),
+        ] {
+            assert_eq!(
+                poly_square_hole.within(&pt),
+                poly_square_hole.within(&p(pt.0, pt.1))
+            );
+            assert_eq!(
+                poly_square_hole.exterior().within(&pt),
+                poly_square_hole.exterior().within(&p(pt.0, pt.1))
+            );
+        }
+    }
+
+    #[test]
+    fn point_matches_coord() {
+        let poly_square_hole = Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+            ])],
+        );
+        for x in -4..=24 {
+            for y in -4..=24 {
+                let pt = Point::new(f64::from(x) / 2.0, f64::from(y) / 2.0);
+                assert_eq!(poly_square_hole.within(&pt), poly_square_hole.within(&pt.0));
+                assert_eq!(
+                    poly_square_hole.exterior().within(&pt),
+                    poly_square_hole.exterior().within(&pt.0)
+                );
+            }
+        }
     }
 
     #[test]
@@ -161,9 +1150,11 @@ mod tests {
         assert!(!poly_strange.within(&p(5.0, 8.0)));
         assert!(!poly_strange.within(&p(-10.0, 5.0)));
         assert!(!poly_strange.within(&p(0.0, 5.0)));
-        assert!(poly_strange.within(&p(10.0, 5.0)));
+        assert!(!poly_strange.within(&p(10.0, 5.0)));
         assert!(poly_strange.within(&p(8.0, 5.0)));
         assert!(!poly_strange.within(&p(10.0, 10.0)));
+        let poly = Polygon::new(poly_strange, vec![]);
+        assert_agree(&poly, &grid(&poly, 0.5));
     }
 
     #[test]
@@ -178,13 +1169,282 @@ mod tests {
             (3.0, 0.0),
         ]
         .into();
-        assert!(!poly_hexagon.within(&p(5.0, 5.0)));
+        assert!(poly_hexagon.within(&p(5.0, 5.0)));
         assert!(poly_hexagon.within(&p(5.0, 8.0)));
         assert!(!poly_hexagon.within(&p(-10.0, 5.0)));
-        assert!(!poly_hexagon.within(&p(0.0, 5.0)));
+        assert!(poly_hexagon.within(&p(0.0, 5.0)));
         assert!(!poly_hexagon.within(&p(10.0, 5.0)));
-        assert!(!poly_hexagon.within(&p(8.0, 5.0)));
-        assert!(poly_hexagon.within(&p(10.0, 10.0)));
+        assert!(poly_hexagon.within(&p(8.0, 5.0)));
+        assert!(!poly_hexagon.within(&p(10.0, 10.0)));
+        let poly = Polygon::new(poly_hexagon, vec![]);
+        assert_agree(&poly, &grid(&poly, 0.5));
+    }
+
+    #[test]
+    fn poly_vertices() {
+        let poly_square: LineString<f64> =
+            vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+        // only the bottom-left corner has the area both right of it and above it
+        assert_eq!(
+            poly_square.within_many(&poly_square.0),
+            vec![true, false, false, false]
+        );
+
+        let poly_hexagon: LineString<f64> = vec![
+            (3.0, 0.0),
+            (7.0, 0.0),
+            (10.0, 5.0),
+            (7.0, 10.0),
+            (3.0, 10.0),
+            (0.0, 5.0),
+        ]
+        .into();
+        assert_eq!(
+            poly_hexagon.within_many(&poly_hexagon.0),
+            vec![true, false, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn multi_poly_squares() {
+        let multi_poly_squares = MultiPolygon(vec![
+            Polygon::new(
+                LineString::from(vec![
+                    (0.0, 0.0),
+                    (10.0, 0.0),
+                    (10.0, 10.0),
+                    (0.0, 10.0),
+                    (0.0, 0.0),
+                ]),
+                vec![LineString::from(vec![
+                    (2.5, 2.5),
+                    (7.5, 2.5),
+                    (7.5, 7.5),
+                    (2.5, 7.5),
+                    (2.5, 2.5),
+                ])],
+            ),
+            Polygon::new(
+                LineString::from(vec![
+                    (20.0, 0.0),
+                    (30.0, 0.0),
+                    (30.0, 10.0),
+                    (20.0, 10.0),
+                    (20.0, 0.0),
+                ]),
+                vec![],
+            ),
+            Polygon::new(
+                LineString::from(vec![
+                    (4.0, 4.0),
+                    (6.0, 4.0),
+                    (6.0, 6.0),
+                    (4.0, 6.0),
+                    (4.0, 4.0),
+                ]),
+                vec![],
+            ),
+        ]);
+        assert!(multi_poly_squares.within(&p(1.0, 5.0)));
+        assert!(multi_poly_squares.within(&p(25.0, 5.0)));
+        assert!(multi_poly_squares.within(&Point::new(25.0, 5.0)));
+        assert!(!multi_poly_squares.within(&p(15.0, 5.0)));
+        assert!(!multi_poly_squares.within(&p(3.0, 5.0)));
+        assert!(multi_poly_squares.within(&p(5.0, 5.0)));
+        assert!(!multi_poly_squares.within(&p(-10.0, 5.0)));
+    }
+
+    #[test]
+    fn triangle() {
+        let triangle = Triangle::new(p(0.0, 0.0), p(10.0, 0.0), p(0.0, 10.0));
+        assert!(triangle.within(&p(2.0, 2.0)));
+        assert!(triangle.within(&Point::new(2.0, 2.0)));
+        assert!(triangle.within(&p(0.0, 5.0)));
+        assert!(triangle.within(&p(5.0, 0.0)));
+        assert!(triangle.within(&p(0.0, 0.0)));
+        assert!(!triangle.within(&p(0.0, 10.0)));
+        assert!(!triangle.within(&p(8.0, 8.0)));
+        assert!(!triangle.within(&p(-10.0, 5.0)));
+    }
+
+    #[test]
+    fn lines() {
+        let poly_square = Vec::from([
+            Line::new(p(0.0, 0.0), p(1.0, 0.0)),
+            Line::new(p(1.0, 1.0), p(1.0, 0.0)),
+            Line::new(p(0.0, 1.0), p(0.0, 0.0)),
+            Line::new(p(1.0, 1.0), p(0.0, 1.0)),
+        ]);
+        assert!(poly_square.within(&p(0.5, 0.5)));
+        assert!(poly_square.within(&Point::new(0.5, 0.5)));
+        assert!(poly_square.within(&p(0.0, 0.5)));
+        assert!(!poly_square.within(&p(1.0, 0.5)));
+        assert!(!poly_square.within(&p(-1.0, 0.5)));
+        assert!(!poly_square.within(&p(0.5, 1.5)));
+
+        let ring: LineString<f64> = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].into();
+        for x in -2..=12 {
+            for y in -2..=12 {
+                let pt = p(f64::from(x) / 10.0, f64::from(y) / 10.0);
+                assert_eq!(poly_square[..].within(&pt), ring.within(&pt));
+            }
+        }
+        assert!(!(&[] as &[Line<f64>]).within(&p(0.5, 0.5)));
+    }
+
+    #[test]
+    fn line() {
+        let segment = Line::new(p(0.0, 0.0), p(10.0, 5.0));
+        assert!(segment.within(&p(0.0, 0.0)));
+        assert!(segment.within(&p(10.0, 5.0)));
+        assert!(segment.within(&p(4.0, 2.0)));
+        assert!(segment.within(&Point::new(4.0, 2.0)));
+        assert!(!segment.within(&p(4.0, 2.1)));
+        assert!(!segment.within(&p(12.0, 6.0)));
+        assert!(!segment.within(&p(-2.0, -1.0)));
+
+        let degenerate = Line::new(p(1.0, 1.0), p(1.0, 1.0));
+        assert!(degenerate.within(&p(1.0, 1.0)));
+        assert!(!degenerate.within(&p(1.0, 2.0)));
+    }
+
+    #[test]
+    fn rect() {
+        let rect = Rect::new(p(0.0, 0.0), p(10.0, 10.0));
+        assert!(rect.within(&p(5.0, 5.0)));
+        assert!(rect.within(&Point::new(5.0, 5.0)));
+        assert!(rect.within(&p(0.0, 5.0)));
+        assert!(!rect.within(&p(10.0, 5.0)));
+        assert!(rect.within(&p(5.0, 0.0)));
+        assert!(!rect.within(&p(5.0, 10.0)));
+        assert!(rect.within(&p(0.0, 0.0)));
+        assert!(!rect.within(&p(10.0, 10.0)));
+        assert!(!rect.within(&p(-10.0, 5.0)));
+        assert!(!rect.within(&p(5.0, 10.5)));
+    }
+
+    #[test]
+    fn geometry() {
+        let square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        let open_square: LineString<f64> =
+            vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+
+        let poly = Geometry::Polygon(Polygon::new(square.clone(), vec![]));
+        assert!(poly.within(&p(5.0, 5.0)));
+        assert!(poly.within(&Point::new(5.0, 5.0)));
+        assert!(!poly.within(&p(-10.0, 5.0)));
+
+        let point = Geometry::Point(Point::new(5.0, 5.0));
+        assert!(!point.within(&p(5.0, 5.0)));
+
+        let line = Geometry::Line(Line::new(p(0.0, 0.0), p(10.0, 10.0)));
+        assert!(line.within(&p(5.0, 5.0)));
+        assert!(!line.within(&p(5.0, 6.0)));
+
+        assert!(Geometry::LineString(square.clone()).within(&p(5.0, 5.0)));
+        assert!(!Geometry::LineString(open_square).within(&p(5.0, 5.0)));
+
+        let collection = Geometry::GeometryCollection(GeometryCollection(vec![point, poly]));
+        assert!(collection.within(&p(5.0, 5.0)));
+        assert!(!collection.within(&p(15.0, 5.0)));
+    }
+
+    #[test]
+    fn poly_square_f32() {
+        let poly_square: LineString<f32> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert!(poly_square.within(&Coord::from((5.0f32, 5.0))));
+        assert!(poly_square.within(&Coord::from((5.0f32, 8.0))));
+        assert!(!poly_square.within(&Coord::from((-10.0f32, 5.0))));
+        assert!(poly_square.within(&Coord::from((0.0f32, 5.0))));
+        assert!(poly_square.within(&Coord::from((8.0f32, 5.0))));
+        assert!(!poly_square.within(&Coord::from((10.0f32, 10.0))));
+    }
+
+    #[test]
+    fn poly_hexagon_f32() {
+        let poly_hexagon: LineString<f32> = vec![
+            (3.0, 0.0),
+            (7.0, 0.0),
+            (10.0, 5.0),
+            (7.0, 10.0),
+            (3.0, 10.0),
+            (0.0, 5.0),
+            (3.0, 0.0),
+        ]
+        .into();
+        assert!(poly_hexagon.within(&Coord::from((5.0f32, 5.0))));
+        assert!(poly_hexagon.within(&Coord::from((5.0f32, 8.0))));
+        assert!(!poly_hexagon.within(&Coord::from((-10.0f32, 5.0))));
+        assert!(poly_hexagon.within(&Coord::from((0.0f32, 5.0))));
+        assert!(!poly_hexagon.within(&Coord::from((10.0f32, 5.0))));
+        assert!(poly_hexagon.within(&Coord::from((8.0f32, 5.0))));
+        assert!(!poly_hexagon.within(&Coord::from((10.0f32, 10.0))));
+    }
+
+    #[test]
+    fn f32_matches_f64() {
+        let rings: Vec<Vec<(f64, f64)>> = vec![
+            vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ],
+            vec![
+                (0.0, 0.0),
+                (2.5, 2.5),
+                (0.0, 10.0),
+                (2.5, 7.5),
+                (7.5, 7.5),
+                (10.0, 10.0),
+                (10.0, 0.0),
+                (2.5, 2.5),
+            ],
+            vec![
+                (3.0, 0.0),
+                (7.0, 0.0),
+                (10.0, 5.0),
+                (7.0, 10.0),
+                (3.0, 10.0),
+                (0.0, 5.0),
+                (3.0, 0.0),
+            ],
+        ];
+        for ring in rings {
+            let ring32: LineString<f32> = ring
+                .iter()
+                .map(|&(x, y)| (x as f32, y as f32))
+                .collect::<Vec<_>>()
+                .into();
+            let ring64: LineString<f64> = ring.into();
+            for x in -8..=28 {
+                for y in -8..=28 {
+                    let (x, y) = (f64::from(x) / 2.0, f64::from(y) / 2.0);
+                    assert_eq!(
+                        ring32.within(&Coord::from((x as f32, y as f32))),
+                        ring64.within(&p(x, y)),
+                        "{} {}",
+                        x,
+                        y
+                    );
+                }
+            }
+        }
     }
 
     #[test]
@@ -301,5 +1561,80 @@ mod tests {
         assert!(cell1.within(&point7));
         assert!(!cell2.within(&point7));
         assert!(!cell3.within(&point7));
+        let points = [point1, point2, point3, point4, point5, point6, point7].map(|pt| pt.0);
+        for cell in [&cell1, &cell2, &cell3] {
+            assert_agree(cell, &points);
+        }
+    }
+
+    mod properties {
+        use crate::{Centroid, RayCasting};
+
+        use geo_types::{Coord, LineString, Polygon};
+        use proptest::prelude::*;
+
+        // small integers, so that translating and scaling by powers of 2 stays exact
+        fn ring() -> impl Strategy<Value = Vec<(i32, i32)>> {
+            prop::collection::vec((-50..50, -50..50), 3..12)
+        }
+
+        fn polygon(ring: &[(i32, i32)], f: impl Fn(f64) -> f64) -> Polygon<f64> {
+            let coords = ring
+                .iter()
+                .map(|&(x, y)| (f(f64::from(x)), f(f64::from(y))))
+                .collect::<Vec<_>>();
+            Polygon::new(LineString::from(coords), vec![])
+        }
+
+        proptest! {
+            #[test]
+            fn translation_invariant(
+                ring in ring(),
+                (x, y) in (-60..60, -60..60),
+                (dx, dy) in (-1000..1000, -1000..1000),
+            ) {
+                let poly = polygon(&ring, |v| v);
+                let moved = Polygon::new(
+                    poly.exterior()
+                        .coords()
+                        .map(|c| (c.x + f64::from(dx), c.y + f64::from(dy)))
+                        .collect(),
+                    vec![],
+                );
+                let pt = Coord::from((f64::from(x), f64::from(y)));
+                let moved_pt = Coord::from((f64::from(x + dx), f64::from(y + dy)));
+                prop_assert_eq!(poly.within(&pt), moved.within(&moved_pt));
+            }
+
+            #[test]
+            fn scale_invariant(
+                ring in ring(),
+                (x, y) in (-60..60, -60..60),
+                exp in -16..16,
+            ) {
+                let factor = 2f64.powi(exp);
+                let poly = polygon(&ring, |v| v);
+                let scaled = polygon(&ring, |v| v * factor);
+                let pt = Coord::from((f64::from(x), f64::from(y)));
+                let scaled_pt = Coord::from((f64::from(x) * factor, f64::from(y) * factor));
+                prop_assert_eq!(poly.within(&pt), scaled.within(&scaled_pt));
+            }
+
+            #[test]
+            fn convex_centroid_inside(
+                mask in (0u16..1 << 12).prop_filter("at least 3 vertices", |m| m.count_ones() >= 3),
+            ) {
+                // vertices picked among 12 evenly spread on a circle always make a convex polygon
+                let coords = (0..12)
+                    .filter(|i| mask & (1 << i) != 0)
+                    .map(|i| {
+                        let angle = f64::from(i) * core::f64::consts::PI / 6.0;
+                        (100.0 * angle.cos(), 100.0 * angle.sin())
+                    })
+                    .collect::<Vec<_>>();
+                let poly = Polygon::new(LineString::from(coords), vec![]);
+                prop_assert!(poly.within(&poly.centroid()));
+            }
+        }
     }
 }
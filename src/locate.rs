@@ -0,0 +1,326 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Tri-state point location, distinguishing boundary points
+
+use geo_types::{Coord, CoordFloat, LineString, Polygon};
+
+use crate::{pt_in_lines, ray_intersects_segment, ring_lines, segment_distance};
+
+/// Position of a point relative to a polygonal area
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Location {
+    /// The point lies strictly inside the area
+    Inside,
+    /// The point lies strictly outside the area
+    Outside,
+    /// The point lies on one of the area's segments
+    Boundary,
+}
+
+/// Trait locating a point inside, outside or on the boundary of a polygonal area
+pub trait Locate<T: CoordFloat, P: Into<Coord<T>>> {
+    /// Locates a point, reporting `Boundary` when it's within `eps` of any segment
+    fn locate_eps(&self, pt: &P, eps: T) -> Location;
+
+    /// Locates a point, reporting `Boundary` when it's within `T::epsilon()` of any segment
+    fn locate(&self, pt: &P) -> Location {
+        self.locate_eps(pt, T::epsilon())
+    }
+
+    /// Checks if a point is within a polygonal area, or within `eps` of its boundary
+    fn within_eps(&self, pt: &P, eps: T) -> bool {
+        self.locate_eps(pt, eps) != Location::Outside
+    }
+
+    /// Checks if a point lies strictly inside the area, boundary points excluded
+    ///
+    /// Unlike `RayCasting::within`, this treats every edge the same way.
+    fn contains_strict(&self, pt: &P) -> bool {
+        self.locate(pt) == Location::Inside
+    }
+
+    /// Checks if a point lies inside the area or on its boundary
+    fn contains_inclusive(&self, pt: &P) -> bool {
+        self.locate(pt) != Location::Outside
+    }
+
+    /// Checks if a point lies within `T::epsilon()` of any segment
+    fn on_boundary(&self, pt: &P) -> bool {
+        self.locate(pt) == Location::Boundary
+    }
+
+    /// Checks if a point lies within `T::epsilon()` of any ring coordinate
+    fn on_vertex(&self, pt: &P) -> bool;
+
+    /// Locates a point like `locate_eps` with `boundary_tol`, along with its signed distance from
+    /// the boundary, negative inside
+    ///
+    /// Both come out of a single pass over the segments. Points located on the boundary get the
+    /// sign of the side they actually lie on, and an empty area reports an infinite distance.
+    fn classify(&self, pt: &P, boundary_tol: T) -> (Location, T);
+}
+
+fn near_vertex<T: CoordFloat>(pt: &Coord<T>, ring: &LineString<T>) -> bool {
+    ring.coords()
+        .any(|coord| (coord.x - pt.x).hypot(coord.y - pt.y) <= T::epsilon())
+}
+
+fn locate_ring<T: CoordFloat>(pt: &Coord<T>, ring: &LineString<T>, eps: T) -> Location {
+    if ring_lines(ring).any(|line| segment_distance(pt, &line) <= eps) {
+        Location::Boundary
+    } else if pt_in_lines(pt, ring_lines(ring)) {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// Crossing parity of the ray cast from `pt` along with the distance from the nearest segment
+fn measure_ring<T: CoordFloat>(pt: &Coord<T>, ring: &LineString<T>) -> (bool, T) {
+    ring_lines(ring).fold((false, T::infinity()), |(inside, distance), line| {
+        (
+            inside != ray_intersects_segment(pt, &line),
+            distance.min(segment_distance(pt, &line)),
+        )
+    })
+}
+
+fn classified<T: CoordFloat>(inside: bool, distance: T, boundary_tol: T) -> (Location, T) {
+    let location = if distance <= boundary_tol {
+        Location::Boundary
+    } else if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    };
+    (location, if inside { -distance } else { distance })
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> Locate<T, P> for LineString<T> {
+    fn locate_eps(&self, pt: &P, eps: T) -> Location {
+        locate_ring(&(*pt).into(), self, eps)
+    }
+
+    fn on_vertex(&self, pt: &P) -> bool {
+        near_vertex(&(*pt).into(), self)
+    }
+
+    fn classify(&self, pt: &P, boundary_tol: T) -> (Location, T) {
+        let (inside, distance) = measure_ring(&(*pt).into(), self);
+        classified(inside, distance, boundary_tol)
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> Locate<T, P> for Polygon<T> {
+    fn locate_eps(&self, pt: &P, eps: T) -> Location {
+        let coord = (*pt).into();
+        match locate_ring(&coord, self.exterior(), eps) {
+            Location::Inside => {}
+            other => return other,
+        }
+
+        let mut location = Location::Inside;
+        for interior in self.interiors() {
+            match locate_ring(&coord, interior, eps) {
+                Location::Boundary => return Location::Boundary,
+                Location::Inside => location = Location::Outside,
+                Location::Outside => {}
+            }
+        }
+        location
+    }
+
+    fn on_vertex(&self, pt: &P) -> bool {
+        let coord = (*pt).into();
+        near_vertex(&coord, self.exterior())
+            || self
+                .interiors()
+                .iter()
+                .any(|interior| near_vertex(&coord, interior))
+    }
+
+    fn classify(&self, pt: &P, boundary_tol: T) -> (Location, T) {
+        let coord = (*pt).into();
+        let (inside, distance) = self.interiors().iter().fold(
+            measure_ring(&coord, self.exterior()),
+            |(inside, distance), interior| {
+                let (in_hole, hole_distance) = measure_ring(&coord, interior);
+                (inside && !in_hole, distance.min(hole_distance))
+            },
+        );
+        classified(inside, distance, boundary_tol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Locate, Location};
+
+    use geo_types::{Coord, LineString, Point, Polygon};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        (x, y).into()
+    }
+
+    fn square() -> LineString<f64> {
+        vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn locate_square() {
+        let poly_square = square();
+        assert_eq!(poly_square.locate(&p(5.0, 5.0)), Location::Inside);
+        assert_eq!(poly_square.locate(&Point::new(5.0, 5.0)), Location::Inside);
+        assert_eq!(poly_square.locate(&p(-10.0, 5.0)), Location::Outside);
+        assert_eq!(poly_square.locate(&p(0.0, 5.0)), Location::Boundary);
+        assert_eq!(poly_square.locate(&p(10.0, 5.0)), Location::Boundary);
+        assert_eq!(poly_square.locate(&p(5.0, 0.0)), Location::Boundary);
+        assert_eq!(poly_square.locate(&p(10.0, 10.0)), Location::Boundary);
+    }
+
+    #[test]
+    fn locate_square_hole() {
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        assert_eq!(poly_square_hole.locate(&p(5.0, 5.0)), Location::Outside);
+        assert_eq!(poly_square_hole.locate(&p(5.0, 8.0)), Location::Inside);
+        assert_eq!(poly_square_hole.locate(&p(-10.0, 5.0)), Location::Outside);
+        assert_eq!(poly_square_hole.locate(&p(0.0, 5.0)), Location::Boundary);
+        assert_eq!(poly_square_hole.locate(&p(2.5, 5.0)), Location::Boundary);
+        assert_eq!(poly_square_hole.locate(&p(7.5, 7.5)), Location::Boundary);
+    }
+
+    #[test]
+    fn within_eps_square() {
+        let poly_square = square();
+        assert!(poly_square.within_eps(&p(0.0, 5.0), 1e-9));
+        assert!(poly_square.within_eps(&p(-1e-10, 5.0), 1e-9));
+        assert!(!poly_square.within_eps(&p(-1e-8, 5.0), 1e-9));
+        assert!(poly_square.within_eps(&p(10.0, 10.0), 1e-9));
+        assert!(poly_square.within_eps(&p(5.0, 5.0), 1e-9));
+        assert!(!poly_square.within_eps(&p(-10.0, 5.0), 1e-9));
+    }
+
+    #[test]
+    fn contains_strict_inclusive_square() {
+        let poly_square = square();
+        assert!(!poly_square.contains_strict(&p(0.0, 5.0)));
+        assert!(poly_square.contains_inclusive(&p(0.0, 5.0)));
+        // the right edge, unlike in `within`, is treated like the left one
+        assert!(!poly_square.contains_strict(&p(10.0, 5.0)));
+        assert!(poly_square.contains_inclusive(&p(10.0, 5.0)));
+        assert!(poly_square.contains_strict(&p(5.0, 5.0)));
+        assert!(poly_square.contains_inclusive(&p(5.0, 5.0)));
+        assert!(!poly_square.contains_strict(&p(-10.0, 5.0)));
+        assert!(!poly_square.contains_inclusive(&p(-10.0, 5.0)));
+    }
+
+    #[test]
+    fn boundary_and_vertex_square() {
+        let poly_square = square();
+        assert!(poly_square.on_boundary(&p(0.0, 0.0)));
+        assert!(poly_square.on_vertex(&p(0.0, 0.0)));
+        assert!(poly_square.on_boundary(&p(5.0, 0.0)));
+        assert!(!poly_square.on_vertex(&p(5.0, 0.0)));
+        assert!(!poly_square.on_boundary(&p(5.0, 5.0)));
+        assert!(!poly_square.on_vertex(&p(5.0, 5.0)));
+        assert!(!poly_square.on_vertex(&p(-1.0, 0.0)));
+
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![LineString::from(vec![(2.5, 2.5), (7.5, 2.5), (7.5, 7.5)])],
+        );
+        assert!(poly_square_hole.on_vertex(&Point::new(10.0, 10.0)));
+        assert!(poly_square_hole.on_vertex(&p(7.5, 2.5)));
+        assert!(poly_square_hole.on_boundary(&p(5.0, 5.0)));
+        assert!(!poly_square_hole.on_vertex(&p(5.0, 5.0)));
+    }
+
+    #[test]
+    fn classify_square() {
+        let poly_square = square();
+        assert_eq!(
+            poly_square.classify(&p(5.0, 5.0), 0.5),
+            (Location::Inside, -5.0)
+        );
+        assert_eq!(
+            poly_square.classify(&p(5.0, -2.0), 0.5),
+            (Location::Outside, 2.0)
+        );
+        assert_eq!(
+            poly_square.classify(&p(-0.5, 5.0), 0.5),
+            (Location::Boundary, 0.5)
+        );
+        assert_eq!(
+            poly_square.classify(&p(0.5, 5.0), 0.5),
+            (Location::Boundary, -0.5)
+        );
+        assert_eq!(
+            poly_square.classify(&p(-0.6, 5.0), 0.5).0,
+            Location::Outside
+        );
+        assert_eq!(
+            LineString::<f64>(vec![]).classify(&p(5.0, 5.0), 0.5),
+            (Location::Outside, f64::INFINITY)
+        );
+
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        assert_eq!(
+            poly_square_hole.classify(&p(5.0, 5.0), 0.5),
+            (Location::Outside, 2.5)
+        );
+        assert_eq!(
+            poly_square_hole.classify(&p(1.0, 5.0), 0.5),
+            (Location::Inside, -1.0)
+        );
+        assert_eq!(
+            poly_square_hole.classify(&p(2.0, 5.0), 0.5),
+            (Location::Boundary, -0.5)
+        );
+        for pt in [p(5.0, 5.0), p(1.0, 5.0), p(2.0, 5.0), p(-5.0, 5.0)] {
+            assert_eq!(
+                poly_square_hole.classify(&pt, 0.5).0,
+                poly_square_hole.locate_eps(&pt, 0.5)
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn location_serde() {
+        for location in [Location::Inside, Location::Outside, Location::Boundary] {
+            let json = serde_json::to_string(&location).unwrap();
+            assert_eq!(serde_json::from_str::<Location>(&json).unwrap(), location);
+        }
+    }
+}
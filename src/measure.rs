@@ -0,0 +1,169 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Planar measures of polygonal areas
+
+use alloc::vec::Vec;
+
+use geo_types::{CoordFloat, LineString, Polygon};
+
+use crate::ring_lines;
+
+/// Trait computing the area of a polygonal area, with the shoelace formula, and its perimeter
+pub trait Area<T: CoordFloat> {
+    /// Area, positive when the exterior ring is wound counter-clockwise and negative otherwise
+    fn signed_area(&self) -> T;
+
+    /// Area, regardless of the rings' orientation
+    fn area(&self) -> T {
+        self.signed_area().abs()
+    }
+
+    /// Total length of the boundary, summing the lengths of every segment
+    fn perimeter(&self) -> T;
+
+    /// Area of each hole, regardless of its orientation, in the order of the interior rings
+    fn holes_info(&self) -> Vec<T>;
+}
+
+impl<T: CoordFloat> Area<T> for LineString<T> {
+    fn signed_area(&self) -> T {
+        let two = T::one() + T::one();
+        ring_lines(self).fold(T::zero(), |area, line| line.determinant() + area) / two
+    }
+
+    /// Rings that aren't explicitly closed include their closing segment.
+    fn perimeter(&self) -> T {
+        ring_lines(self).fold(T::zero(), |length, line| {
+            let delta = line.delta();
+            length + delta.x.hypot(delta.y)
+        })
+    }
+
+    /// A lone ring has no holes.
+    fn holes_info(&self) -> Vec<T> {
+        Vec::new()
+    }
+}
+
+/// Holes are subtracted from the exterior, whatever their orientation
+impl<T: CoordFloat> Area<T> for Polygon<T> {
+    fn signed_area(&self) -> T {
+        let exterior = self.exterior().signed_area();
+        let holes = self
+            .interiors()
+            .iter()
+            .fold(T::zero(), |area, ring| area + ring.area());
+        let area = exterior.abs() - holes;
+        if exterior < T::zero() {
+            -area
+        } else {
+            area
+        }
+    }
+
+    /// Holes are part of the boundary, so their lengths are added to the exterior's.
+    fn perimeter(&self) -> T {
+        self.interiors()
+            .iter()
+            .fold(self.exterior().perimeter(), |length, ring| {
+                length + ring.perimeter()
+            })
+    }
+
+    fn holes_info(&self) -> Vec<T> {
+        self.interiors().iter().map(Area::area).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Area;
+
+    use geo_types::{LineString, Polygon};
+
+    fn square() -> LineString<f64> {
+        vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn area_square() {
+        let poly_square = square();
+        assert_eq!(poly_square.signed_area(), 100.0);
+        assert_eq!(poly_square.area(), 100.0);
+
+        let mut reversed = poly_square.clone();
+        reversed.0.reverse();
+        assert_eq!(reversed.signed_area(), -100.0);
+        assert_eq!(reversed.area(), 100.0);
+
+        let open: LineString<f64> = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+        assert_eq!(open.area(), 100.0);
+        assert_eq!(LineString::<f64>(vec![]).area(), 0.0);
+    }
+
+    #[test]
+    fn area_square_hole() {
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        assert_eq!(poly_square_hole.signed_area(), 75.0);
+        assert_eq!(poly_square_hole.area(), 75.0);
+        assert_eq!(poly_square_hole.perimeter(), 60.0);
+        assert_eq!(poly_square_hole.exterior().perimeter(), 40.0);
+        assert_eq!(poly_square_hole.holes_info(), vec![25.0]);
+    }
+
+    #[test]
+    fn holes_info_square() {
+        let poly_square = square();
+        assert!(poly_square.holes_info().is_empty());
+        assert!(Polygon::new(poly_square.clone(), vec![])
+            .holes_info()
+            .is_empty());
+
+        let mut reversed: LineString<f64> =
+            vec![(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)].into();
+        reversed.0.reverse();
+        let poly_two_holes = Polygon::new(
+            poly_square,
+            vec![
+                reversed,
+                vec![(5.0, 5.0), (9.0, 5.0), (9.0, 9.0), (5.0, 9.0)].into(),
+            ],
+        );
+        assert_eq!(poly_two_holes.holes_info(), vec![4.0, 16.0]);
+        assert_eq!(poly_two_holes.area(), 80.0);
+    }
+
+    #[test]
+    fn perimeter_square() {
+        let poly_square = square();
+        assert_eq!(poly_square.perimeter(), 40.0);
+        assert_eq!(Polygon::new(poly_square, vec![]).perimeter(), 40.0);
+
+        let open: LineString<f64> = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+        assert_eq!(open.perimeter(), 40.0);
+        let triangle: LineString<f64> = vec![(0.0, 0.0), (3.0, 0.0), (3.0, 4.0)].into();
+        assert_eq!(triangle.perimeter(), 12.0);
+        assert_eq!(LineString::<f64>(vec![]).perimeter(), 0.0);
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Per-member queries on multipolygons
+
+use geo_types::{Coord, CoordFloat, CoordNum, MultiPolygon, Point, Polygon};
+
+use crate::{locate_in, Area, Centroid, RayCasting};
+
+/// Trait querying the members of a multipolygon one by one, where `RayCasting::within` only
+/// tells if any contains the point
+pub trait MultiRayCasting<T: CoordFloat, P: Into<Coord<T>>> {
+    /// Fraction of the members containing a point, from 0 to 1
+    ///
+    /// Members may overlap, so this scores how many of them agree on the point. An empty
+    /// multipolygon contains nothing, hence scores 0.
+    fn membership(&self, pt: &P) -> T;
+
+    /// Index of the first member containing a point, if any, like `locate_in` over
+    /// `Members::members`
+    fn member_containing(&self, pt: &P) -> Option<usize>;
+}
+
+/// Trait exposing the member polygons of a multipolygon
+///
+/// This is kept apart from `MultiRayCasting`, since it doesn't depend on the point type.
+pub trait Members<T: CoordNum> {
+    /// Member polygons, in order, indexed like `MultiRayCasting::member_containing` reports them
+    fn members(&self) -> &[Polygon<T>];
+
+    /// Point within the member with the largest area, the first of them on ties, or `None` when
+    /// there's no member
+    ///
+    /// This is `Centroid::representative_point` of that member, so it's within the
+    /// multipolygon as reported by `RayCasting::within` as long as the member has some surface.
+    fn representative_point(&self) -> Option<Point<T>>
+    where
+        T: CoordFloat,
+    {
+        self.members()
+            .iter()
+            .map(|poly| (poly.area(), poly))
+            .fold(None, |best, candidate| match best {
+                Some((area, _)) if area >= candidate.0 => best,
+                _ => Some(candidate),
+            })
+            .map(|(_, poly)| poly.representative_point())
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> MultiRayCasting<T, P> for MultiPolygon<T> {
+    fn membership(&self, pt: &P) -> T {
+        let coord = (*pt).into();
+        let count = self.iter().filter(|poly| poly.within(&coord)).count();
+        match (T::from(count), T::from(self.0.len())) {
+            (Some(count), Some(total)) if total > T::zero() => count / total,
+            _ => T::zero(),
+        }
+    }
+
+    fn member_containing(&self, pt: &P) -> Option<usize> {
+        locate_in(&(*pt).into(), &self.0)
+    }
+}
+
+impl<T: CoordNum> Members<T> for MultiPolygon<T> {
+    fn members(&self) -> &[Polygon<T>] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Members, MultiRayCasting};
+    use crate::RayCasting;
+
+    use geo_types::{Coord, LineString, MultiPolygon, Point, Polygon};
+
+    fn square(min: (f64, f64), size: f64) -> Polygon<f64> {
+        Polygon::new(
+            LineString::from(vec![
+                min,
+                (min.0 + size, min.1),
+                (min.0 + size, min.1 + size),
+                (min.0, min.1 + size),
+            ]),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn membership_overlapping() {
+        let multi = MultiPolygon(vec![
+            square((0.0, 0.0), 10.0),
+            square((5.0, 5.0), 10.0),
+            square((20.0, 20.0), 10.0),
+            square((-10.0, -10.0), 5.0),
+        ]);
+        assert_eq!(multi.membership(&Coord::from((7.5, 7.5))), 0.5);
+        assert_eq!(multi.membership(&Point::new(2.5, 2.5)), 0.25);
+        assert_eq!(multi.membership(&(50.0, 50.0)), 0.0);
+
+        let same = MultiPolygon(vec![square((0.0, 0.0), 10.0); 3]);
+        assert_eq!(same.membership(&(5.0, 5.0)), 1.0);
+        assert_eq!(MultiPolygon::<f64>(vec![]).membership(&(5.0, 5.0)), 0.0);
+    }
+
+    #[test]
+    fn member_containing_two() {
+        let multi = MultiPolygon(vec![square((0.0, 0.0), 10.0), square((20.0, 0.0), 10.0)]);
+        assert_eq!(multi.members().len(), 2);
+        assert_eq!(multi.members()[1], square((20.0, 0.0), 10.0));
+        assert_eq!(multi.member_containing(&Coord::from((5.0, 5.0))), Some(0));
+        assert_eq!(multi.member_containing(&Point::new(25.0, 5.0)), Some(1));
+        assert_eq!(multi.member_containing(&(15.0, 5.0)), None);
+        for (index, member) in multi.members().iter().enumerate() {
+            let center = member.exterior().0[0] + Coord::from((5.0, 5.0));
+            assert_eq!(multi.member_containing(&center), Some(index));
+        }
+    }
+
+    #[test]
+    fn representative_point_largest() {
+        let poly_u = Polygon::new(
+            LineString::from(vec![
+                (20.0, 0.0),
+                (30.0, 0.0),
+                (30.0, 10.0),
+                (27.0, 10.0),
+                (27.0, 3.0),
+                (23.0, 3.0),
+                (23.0, 10.0),
+                (20.0, 10.0),
+            ]),
+            vec![],
+        );
+        let multi = MultiPolygon(vec![
+            square((0.0, 0.0), 2.0),
+            poly_u,
+            square((-5.0, 0.0), 3.0),
+        ]);
+        let pt = multi.representative_point().unwrap();
+        assert!(multi.within(&pt));
+        assert!(multi.members()[1].within(&pt));
+        assert!(pt.x() > 20.0);
+
+        let tied = MultiPolygon(vec![square((0.0, 0.0), 2.0), square((5.0, 0.0), 2.0)]);
+        let pt = tied.representative_point().unwrap();
+        assert!(tied.members()[0].within(&pt));
+        assert_eq!(MultiPolygon::<f64>(vec![]).representative_point(), None);
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Ring orientation detection and normalization
+//!
+//! Ray Casting doesn't depend on orientation, so none of this changes `RayCasting::within`
+//! results, but area signs and the winding number do.
+
+use geo_types::{CoordFloat, LineString, Polygon};
+
+use crate::{Area, Ring};
+
+/// Trait detecting and normalizing the winding direction of rings
+pub trait Orientation<T: CoordFloat> {
+    /// Checks if the (exterior) ring is wound counter-clockwise, degenerate rings with no area
+    /// aren't
+    fn is_ccw(&self) -> bool;
+
+    /// Copy with the exterior ring wound counter-clockwise, and interior rings clockwise
+    fn normalized(&self) -> Self;
+}
+
+fn oriented<T: CoordFloat>(ring: &LineString<T>, ccw: bool) -> LineString<T> {
+    let mut ring = ring.clone();
+    if ring.is_ccw() != ccw {
+        ring.reverse_ring();
+    }
+    ring
+}
+
+impl<T: CoordFloat> Orientation<T> for LineString<T> {
+    fn is_ccw(&self) -> bool {
+        self.signed_area() > T::zero()
+    }
+
+    fn normalized(&self) -> Self {
+        oriented(self, true)
+    }
+}
+
+impl<T: CoordFloat> Orientation<T> for Polygon<T> {
+    fn is_ccw(&self) -> bool {
+        self.exterior().is_ccw()
+    }
+
+    fn normalized(&self) -> Self {
+        Polygon::new(
+            oriented(self.exterior(), true),
+            self.interiors()
+                .iter()
+                .map(|ring| oriented(ring, false))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Orientation;
+    use crate::RayCasting;
+
+    use geo_types::{Coord, LineString, Polygon};
+
+    fn square() -> LineString<f64> {
+        vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into()
+    }
+
+    fn hole() -> LineString<f64> {
+        vec![(2.5, 2.5), (7.5, 2.5), (7.5, 7.5), (2.5, 7.5), (2.5, 2.5)].into()
+    }
+
+    #[test]
+    fn is_ccw_square() {
+        let poly_square = square();
+        assert!(poly_square.is_ccw());
+
+        let mut reversed = poly_square.clone();
+        reversed.0.reverse();
+        assert!(!reversed.is_ccw());
+        assert_eq!(reversed.normalized(), poly_square);
+        assert_eq!(poly_square.normalized(), poly_square);
+    }
+
+    #[test]
+    fn normalized_square_hole() {
+        // the hole is wound the same direction as the exterior
+        let poly_square_hole = Polygon::new(square(), vec![hole()]);
+        assert!(poly_square_hole.is_ccw());
+        assert!(poly_square_hole.interiors()[0].is_ccw());
+
+        let normalized = poly_square_hole.normalized();
+        assert!(normalized.is_ccw());
+        assert!(!normalized.interiors()[0].is_ccw());
+
+        for pt in [(5.0, 5.0), (5.0, 8.0), (-10.0, 5.0), (0.0, 5.0), (8.0, 5.0)] {
+            let pt = Coord::from(pt);
+            assert_eq!(normalized.within(&pt), poly_square_hole.within(&pt));
+        }
+    }
+}
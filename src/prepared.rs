@@ -0,0 +1,331 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Precomputed polygon query structure
+
+use alloc::vec::Vec;
+
+use geo_types::{Coord, CoordFloat, Line, LineString, MultiPolygon, Point, Polygon, Rect};
+
+use crate::{bounding_rect, edges, is_degenerate, pt_in_lines, ray_misses_rect, RayCasting};
+
+/// Polygon prepared for repeated containment queries
+///
+/// Every segment of the exterior and interior rings, but the zero-length ones, is copied once at
+/// construction, along with the exterior's bounding box. This roughly doubles the memory held by
+/// the rings, in exchange each query skips building the segments again, and points outside the
+/// bounding box are rejected with four comparisons.
+///
+/// Nothing is cached or mutated after construction, queries only borrow `&self`, so a prepared
+/// polygon is `Send + Sync` and can be shared as is across threads, typically behind an `Arc`:
+///
+/// ```
+/// use std::{sync::Arc, thread};
+///
+/// use geo_raycasting::PreparedPolygon;
+/// use geo_types::{LineString, Polygon};
+///
+/// let square: LineString<f64> = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+/// let prepared = Arc::new(PreparedPolygon::new(&Polygon::new(square, vec![])));
+///
+/// let handles = (0..4)
+///     .map(|i| {
+///         let prepared = Arc::clone(&prepared);
+///         thread::spawn(move || prepared.contains(&(f64::from(i) * 4.0, 5.0).into()))
+///     })
+///     .collect::<Vec<_>>();
+/// let found = handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>();
+/// assert_eq!(found, vec![true, true, true, false]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PreparedPolygon<T: CoordFloat> {
+    exterior: Vec<Line<T>>,
+    interiors: Vec<Vec<Line<T>>>,
+    bounding_rect: Option<Rect<T>>,
+}
+
+impl<T: CoordFloat> PreparedPolygon<T> {
+    /// Prepares a polygon, caching its segments and bounding box
+    pub fn new(poly: &Polygon<T>) -> Self {
+        // degenerate rings contain nothing, like in `RayCasting::within`
+        let lines = |ring: &LineString<T>| {
+            if is_degenerate(&ring.0) {
+                Vec::new()
+            } else {
+                edges(ring).collect()
+            }
+        };
+        let exterior = lines(poly.exterior());
+        PreparedPolygon {
+            bounding_rect: bounding_rect(exterior.iter().map(|line| &line.start)),
+            exterior,
+            interiors: poly.interiors().iter().map(lines).collect(),
+        }
+    }
+
+    /// Checks if a point is within the polygon, with the same result as `RayCasting::within`
+    pub fn contains(&self, pt: &Coord<T>) -> bool {
+        self.bounding_rect
+            .is_some_and(|rect| !ray_misses_rect(pt, &rect))
+            && pt_in_lines(pt, self.exterior.iter().copied())
+            && !self
+                .interiors
+                .iter()
+                .any(|ring| pt_in_lines(pt, ring.iter().copied()))
+    }
+}
+
+impl<T: CoordFloat> From<&Polygon<T>> for PreparedPolygon<T> {
+    fn from(poly: &Polygon<T>) -> Self {
+        PreparedPolygon::new(poly)
+    }
+}
+
+impl<T: CoordFloat> RayCasting<T, Point<T>> for PreparedPolygon<T> {
+    fn within(&self, pt: &Point<T>) -> bool {
+        self.contains(&pt.0)
+    }
+}
+
+impl<T: CoordFloat> RayCasting<T, Coord<T>> for PreparedPolygon<T> {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        self.contains(pt)
+    }
+}
+
+/// MultiPolygon prepared for repeated containment queries
+///
+/// Each member is prepared as a `PreparedPolygon`, keeping its own bounding box, and the box
+/// enclosing them all is stored as well. Points outside of it are rejected at once, and the
+/// others only run the full ray cast against the members whose box contains them.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PreparedMultiPolygon<T: CoordFloat> {
+    members: Vec<PreparedPolygon<T>>,
+    bounding_rect: Option<Rect<T>>,
+}
+
+impl<T: CoordFloat> PreparedMultiPolygon<T> {
+    /// Prepares every member of a multipolygon
+    pub fn new(multi: &MultiPolygon<T>) -> Self {
+        let members = multi.iter().map(PreparedPolygon::new).collect::<Vec<_>>();
+        let corners = members
+            .iter()
+            .filter_map(|member| member.bounding_rect)
+            .flat_map(|rect| [rect.min(), rect.max()])
+            .collect::<Vec<_>>();
+        PreparedMultiPolygon {
+            bounding_rect: bounding_rect(&corners),
+            members,
+        }
+    }
+
+    /// Checks if a point is within any member, with the same result as `RayCasting::within`
+    pub fn contains(&self, pt: &Coord<T>) -> bool {
+        self.bounding_rect
+            .is_some_and(|rect| !ray_misses_rect(pt, &rect))
+            && self.members.iter().any(|member| member.contains(pt))
+    }
+}
+
+impl<T: CoordFloat> From<&MultiPolygon<T>> for PreparedMultiPolygon<T> {
+    fn from(multi: &MultiPolygon<T>) -> Self {
+        PreparedMultiPolygon::new(multi)
+    }
+}
+
+impl<T: CoordFloat> RayCasting<T, Point<T>> for PreparedMultiPolygon<T> {
+    fn within(&self, pt: &Point<T>) -> bool {
+        self.contains(&pt.0)
+    }
+}
+
+impl<T: CoordFloat> RayCasting<T, Coord<T>> for PreparedMultiPolygon<T> {
+    fn within(&self, pt: &Coord<T>) -> bool {
+        self.contains(pt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PreparedMultiPolygon, PreparedPolygon};
+    use crate::RayCasting;
+
+    use std::{sync::Arc, thread};
+
+    use geo_types::{Coord, LineString, MultiPolygon, Polygon};
+
+    fn grid() -> impl Iterator<Item = Coord<f64>> {
+        (-4..=28)
+            .flat_map(|x| (-4..=28).map(move |y| (f64::from(x) / 2.0, f64::from(y) / 2.0).into()))
+    }
+
+    fn assert_matches(poly: &Polygon<f64>) {
+        let prepared = PreparedPolygon::new(poly);
+        for pt in grid() {
+            assert_eq!(prepared.contains(&pt), poly.within(&pt), "{:?}", pt);
+        }
+    }
+
+    #[test]
+    fn prepared_square_hole() {
+        assert_matches(&Polygon::new(
+            LineString::from(vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ]),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        ));
+    }
+
+    #[test]
+    fn prepared_strange() {
+        assert_matches(&Polygon::new(
+            LineString::from(vec![
+                (0.0, 0.0),
+                (2.5, 2.5),
+                (0.0, 10.0),
+                (2.5, 7.5),
+                (7.5, 7.5),
+                (10.0, 10.0),
+                (10.0, 0.0),
+                (2.5, 2.5),
+            ]),
+            vec![],
+        ));
+    }
+
+    #[test]
+    fn prepared_hexagon() {
+        assert_matches(&Polygon::new(
+            LineString::from(vec![
+                (3.0, 0.0),
+                (7.0, 0.0),
+                (10.0, 5.0),
+                (7.0, 10.0),
+                (3.0, 10.0),
+                (0.0, 5.0),
+                (3.0, 0.0),
+            ]),
+            vec![],
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn prepared_serde() {
+        let poly = Polygon::new(
+            LineString::from(vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ]),
+            vec![],
+        );
+        let prepared = PreparedPolygon::new(&poly);
+        let json = serde_json::to_string(&prepared).unwrap();
+        let restored: PreparedPolygon<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, prepared);
+        assert!(restored.contains(&(5.0, 5.0).into()));
+    }
+
+    #[test]
+    fn prepared_multi() {
+        let squares = MultiPolygon(
+            [(0.0, 0.0), (20.0, 0.0), (0.0, 20.0)]
+                .iter()
+                .map(|&(x, y)| {
+                    Polygon::new(
+                        LineString::from(vec![
+                            (x, y),
+                            (x + 10.0, y),
+                            (x + 10.0, y + 10.0),
+                            (x, y + 10.0),
+                        ]),
+                        vec![LineString::from(vec![
+                            (x + 2.5, y + 2.5),
+                            (x + 7.5, y + 2.5),
+                            (x + 7.5, y + 7.5),
+                            (x + 2.5, y + 7.5),
+                        ])],
+                    )
+                })
+                .collect(),
+        );
+        let prepared = PreparedMultiPolygon::new(&squares);
+        assert_eq!(
+            prepared.bounding_rect,
+            Some(geo_types::Rect::new((0.0, 0.0), (30.0, 30.0)))
+        );
+        for x in -4..=64 {
+            for y in -4..=64 {
+                let pt = (f64::from(x) / 2.0, f64::from(y) / 2.0).into();
+                assert_eq!(prepared.contains(&pt), squares.within(&pt), "{:?}", pt);
+            }
+        }
+        // outside the box of every member, and of the whole, but on the ray of all of them
+        assert!(!prepared.within(&Coord::from((-100.0, 5.0))));
+        assert!(!prepared.within(&Coord::from((25.0, 25.0))));
+        assert!(prepared.within(&Coord::from((21.0, 5.0))));
+
+        let empty = PreparedMultiPolygon::new(&MultiPolygon::<f64>(vec![]));
+        assert!(!empty.contains(&(0.0, 0.0).into()));
+    }
+
+    #[test]
+    fn prepared_shared() {
+        fn assert_send_sync<S: Send + Sync>() {}
+        assert_send_sync::<PreparedPolygon<f64>>();
+        assert_send_sync::<PreparedMultiPolygon<f32>>();
+
+        let poly = Polygon::new(
+            LineString::from(vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ]),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        let prepared = Arc::new(PreparedPolygon::new(&poly));
+        let handles = (0..8)
+            .map(|_| {
+                let prepared = Arc::clone(&prepared);
+                thread::spawn(move || grid().map(|pt| prepared.contains(&pt)).collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        let expected = grid().map(|pt| poly.within(&pt)).collect::<Vec<_>>();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn prepared_empty() {
+        let prepared = PreparedPolygon::new(&Polygon::new(LineString(vec![]), vec![]));
+        assert!(!prepared.contains(&(0.0, 0.0).into()));
+    }
+}
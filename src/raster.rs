@@ -0,0 +1,220 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Scanline rasterization of polygonal areas into grids
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use geo_types::{Coord, CoordFloat, Line, LineString, Polygon, Rect};
+
+use crate::{edges, is_degenerate, is_left};
+
+/// Trait filling a polygonal area into a grid of cells
+pub trait Rasterize<T: CoordFloat> {
+    /// Mask of the cells of a `cols` by `rows` grid spanning `bounds` whose center is within the
+    /// area, row by row starting from the minimum Y, each row starting from the minimum X
+    ///
+    /// Every row is filled from the segments it crosses alone, finding the cells left of each
+    /// segment with a binary search, instead of casting a ray from every cell. The same predicate
+    /// as `RayCasting::within` is used, so the mask is exactly the one it would give.
+    fn rasterize(&self, bounds: Rect<T>, cols: usize, rows: usize) -> Vec<bool>;
+}
+
+/// Centers of `count` cells evenly splitting the range starting at `min` and `size` wide
+fn centers<T: CoordFloat>(min: T, size: T, count: usize) -> Vec<T> {
+    let two = T::one() + T::one();
+    let cells = T::from(count).unwrap() * two;
+    (0..count)
+        .map(|index| min + size * (T::from(index).unwrap() * two + T::one()) / cells)
+        .collect()
+}
+
+/// Fills `row` with the even-odd parity of the cells centered at `xs` and `y` against `lines`
+fn fill_row<T: CoordFloat>(lines: &[Line<T>], y: T, xs: &[T], row: &mut [bool]) {
+    row.fill(false);
+    for line in lines {
+        let (a, b) = if line.start.y > line.end.y {
+            (&line.end, &line.start)
+        } else {
+            (&line.start, &line.end)
+        };
+        if a.y <= y && y < b.y {
+            // the cross product decreases along the row, so the cells left of it are a prefix
+            let left = xs.partition_point(|&x| is_left(a, b, &Coord { x, y }));
+            if left > 0 {
+                row[left - 1] = !row[left - 1];
+            }
+        }
+    }
+    // each mark flips the parity of every cell up to it
+    let mut parity = false;
+    for cell in row.iter_mut().rev() {
+        parity ^= *cell;
+        *cell = parity;
+    }
+}
+
+/// Segments of a ring able to contain points, `None` for degenerate rings
+fn ring_edges<T: CoordFloat>(ring: &LineString<T>) -> Option<Vec<Line<T>>> {
+    if is_degenerate(&ring.0) {
+        None
+    } else {
+        Some(edges(ring).collect())
+    }
+}
+
+fn rasterize_rings<T: CoordFloat>(
+    exterior: &LineString<T>,
+    interiors: &[LineString<T>],
+    bounds: Rect<T>,
+    cols: usize,
+    rows: usize,
+) -> Vec<bool> {
+    let mut mask = vec![false; cols * rows];
+    let exterior = match ring_edges(exterior) {
+        Some(lines) => lines,
+        None => return mask,
+    };
+    let holes = interiors.iter().filter_map(ring_edges).collect::<Vec<_>>();
+
+    let xs = centers(bounds.min().x, bounds.width(), cols);
+    let ys = centers(bounds.min().y, bounds.height(), rows);
+    let mut hole_row = vec![false; cols];
+    for (row, &y) in mask.chunks_mut(cols.max(1)).zip(&ys) {
+        fill_row(&exterior, y, &xs, row);
+        for hole in &holes {
+            fill_row(hole, y, &xs, &mut hole_row);
+            for (cell, in_hole) in row.iter_mut().zip(&hole_row) {
+                *cell = *cell && !in_hole;
+            }
+        }
+    }
+    mask
+}
+
+impl<T: CoordFloat> Rasterize<T> for LineString<T> {
+    fn rasterize(&self, bounds: Rect<T>, cols: usize, rows: usize) -> Vec<bool> {
+        rasterize_rings(self, &[], bounds, cols, rows)
+    }
+}
+
+impl<T: CoordFloat> Rasterize<T> for Polygon<T> {
+    fn rasterize(&self, bounds: Rect<T>, cols: usize, rows: usize) -> Vec<bool> {
+        rasterize_rings(self.exterior(), self.interiors(), bounds, cols, rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rasterize;
+    use crate::RayCasting;
+
+    use geo_types::{Coord, LineString, Polygon, Rect};
+
+    fn square() -> LineString<f64> {
+        vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into()
+    }
+
+    /// Compares the mask against per-cell `within` calls
+    fn assert_matches<G: Rasterize<f64> + RayCasting<f64, Coord<f64>>>(
+        geom: &G,
+        bounds: Rect<f64>,
+        cols: usize,
+        rows: usize,
+    ) {
+        let mask = geom.rasterize(bounds, cols, rows);
+        assert_eq!(mask.len(), cols * rows);
+        let center = |min: f64, size: f64, index: usize, count: usize| {
+            min + size * (index as f64 * 2.0 + 1.0) / (count as f64 * 2.0)
+        };
+        for row in 0..rows {
+            for col in 0..cols {
+                let center = Coord {
+                    x: center(bounds.min().x, bounds.width(), col, cols),
+                    y: center(bounds.min().y, bounds.height(), row, rows),
+                };
+                assert_eq!(mask[row * cols + col], geom.within(&center), "{:?}", center);
+            }
+        }
+    }
+
+    #[test]
+    fn rasterize_square() {
+        let poly_square = square();
+        let mask = poly_square.rasterize(Rect::new((-5.0, -5.0), (15.0, 15.0)), 10, 10);
+        assert_eq!(mask.iter().filter(|cell| **cell).count(), 25);
+        assert_matches(&poly_square, Rect::new((-5.0, -5.0), (15.0, 15.0)), 10, 10);
+        // cell centers on every integer, so right on the edges and vertices
+        assert_matches(&poly_square, Rect::new((-0.5, -0.5), (10.5, 10.5)), 11, 11);
+        assert_matches(&poly_square, Rect::new((2.0, 3.0), (4.0, 5.0)), 7, 3);
+        assert!(poly_square
+            .rasterize(Rect::new((0.0, 0.0), (10.0, 10.0)), 0, 10)
+            .is_empty());
+    }
+
+    #[test]
+    fn rasterize_hexagon_hole() {
+        let poly_hexagon: LineString<f64> = vec![
+            (3.0, 0.0),
+            (7.0, 0.0),
+            (10.0, 5.0),
+            (7.0, 10.0),
+            (3.0, 10.0),
+            (0.0, 5.0),
+        ]
+        .into();
+        assert_matches(&poly_hexagon, Rect::new((-1.0, -1.0), (11.0, 11.0)), 48, 48);
+        assert_matches(&poly_hexagon, Rect::new((-0.5, -0.5), (10.5, 10.5)), 11, 11);
+
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![vec![(2.5, 2.5), (7.5, 2.5), (7.5, 7.5), (2.5, 7.5)].into()],
+        );
+        assert_matches(
+            &poly_square_hole,
+            Rect::new((-1.0, -1.0), (11.0, 11.0)),
+            24,
+            24,
+        );
+        assert_matches(
+            &poly_square_hole,
+            Rect::new((-0.25, -0.25), (10.25, 10.25)),
+            21,
+            21,
+        );
+    }
+
+    #[test]
+    fn rasterize_strange() {
+        let poly_strange: LineString<f64> = vec![
+            (0.0, 0.0),
+            (2.5, 2.5),
+            (0.0, 10.0),
+            (2.5, 7.5),
+            (7.5, 7.5),
+            (10.0, 10.0),
+            (10.0, 0.0),
+            (2.5, 2.5),
+        ]
+        .into();
+        assert_matches(&poly_strange, Rect::new((-1.0, -1.0), (11.0, 11.0)), 36, 36);
+
+        let degenerate: LineString<f64> = vec![(0.0, 0.0), (10.0, 10.0), (5.0, 5.0)].into();
+        assert!(degenerate
+            .rasterize(Rect::new((0.0, 0.0), (10.0, 10.0)), 10, 10)
+            .iter()
+            .all(|cell| !cell));
+    }
+}
@@ -0,0 +1,453 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Ray Casting along arbitrary directions
+
+use alloc::vec::Vec;
+
+use geo_types::{Coord, CoordFloat, Line, LineString, Polygon};
+
+use crate::{ring_lines, RayCasting};
+
+/// Directions tried in turn by `within_robust`, the first one is the usual +X ray
+const DIRECTIONS: [(f64, f64); 4] = [
+    (1.0, 0.0),
+    (0.923_879_532_511_286_7, 0.382_683_432_365_089_8),
+    (-0.382_683_432_365_089_8, 0.923_879_532_511_286_7),
+    (-0.555_570_233_019_602_2, -0.831_469_612_302_545_2),
+];
+
+/// Trait implementing Ray Casting algorithm, avoiding rays that hit vertices
+pub trait RobustRayCasting<T: CoordFloat, P: Into<Coord<T>>> {
+    /// Checks if a point is within a polygonal area
+    ///
+    /// A ray passing exactly through a vertex, or running along a segment, needs a tie-breaking
+    /// rule, like the half-open convention of `RayCasting::within`. When the +X ray hits such a
+    /// case it is cast again along a few rotated directions, until one misses every vertex. If
+    /// every direction is degenerate, as expected for points on the boundary, the answer of
+    /// `RayCasting::within` is returned.
+    ///
+    /// The half-open convention already counts vertex hits exactly, so both agree on every point
+    /// off the boundary: this only matters as a different tie-break for boundary points, where
+    /// rounding may let a rotated ray through and pick either side.
+    fn within_robust(&self, pt: &P) -> bool;
+
+    /// Checks if a point is within a polygonal area, casting the ray along `ray_dir`
+    ///
+    /// The direction doesn't need to be normalized. Vertices and segments on the ray follow the
+    /// half-open convention of `RayCasting::within`, rotated along with the ray, so
+    /// `within_with_ray(pt, (1, 0))` matches `within(pt)`. Interior points get the same answer for
+    /// every direction, boundary points may not. A zero direction crosses nothing, hence returns
+    /// `false`.
+    fn within_with_ray(&self, pt: &P, ray_dir: (T, T)) -> bool;
+
+    /// Points where the ray cast from `origin` along `dir` crosses the boundary, sorted by
+    /// distance from `origin`
+    ///
+    /// These are the crossings counted by `within_with_ray`, so segments touched by the ray
+    /// follow the same half-open convention. For a ring, or a polygon whose holes lie apart
+    /// within its exterior, their number is odd exactly when `within_with_ray(origin, dir)` is
+    /// `true`.
+    fn ray_intersections(&self, origin: &P, dir: (T, T)) -> Vec<Coord<T>>;
+
+    /// Index of the nearest segment crossed by the +X ray cast from `pt`, the one
+    /// `RayCasting::within` casts
+    ///
+    /// Segments are indexed in ring order, as yielded by `Ring::closed_lines`, and follow the
+    /// half-open convention. `None` means the ray escapes without crossing any, so `pt` is outside.
+    fn first_crossing_edge(&self, pt: &P) -> Option<usize>;
+}
+
+/// Counts the segments crossed by a ray cast from `pt` along `dir`
+///
+/// Returns `None` when the ray passes through a vertex or `pt` lies on a segment, since the
+/// crossing can't be told apart there.
+fn ray_crossings<T: CoordFloat, I: IntoIterator<Item = Line<T>>>(
+    pt: &Coord<T>,
+    lines: I,
+    dir: (T, T),
+) -> Option<usize> {
+    let zero = T::zero();
+    let side = |c: Coord<T>| dir.0 * c.y - dir.1 * c.x;
+    let along = |c: Coord<T>| dir.0 * c.x + dir.1 * c.y;
+
+    let mut count = 0;
+    for line in lines {
+        let (a, b) = (line.start - *pt, line.end - *pt);
+        let (side_a, side_b) = (side(a), side(b));
+        if (side_a == zero && along(a) >= zero) || (side_b == zero && along(b) >= zero) {
+            return None;
+        }
+        if (side_a < zero && side_b > zero) || (side_a > zero && side_b < zero) {
+            let s = side_a / (side_a - side_b);
+            let hit = along(a) + s * (along(b) - along(a));
+            if hit == zero {
+                return None;
+            } else if hit > zero {
+                count += 1;
+            }
+        }
+    }
+    Some(count)
+}
+
+/// Segments crossed by a ray cast from `pt` along `dir`, with the half-open convention, each
+/// with the crossing position along the ray and the crossing point
+///
+/// Measuring sides as the height over the ray, a segment counts when its lower end is at or below
+/// the ray, its upper end strictly above it, and it's crossed strictly ahead of `pt`.
+fn half_open_hits<T: CoordFloat, I: IntoIterator<Item = Line<T>>>(
+    pt: Coord<T>,
+    lines: I,
+    dir: (T, T),
+) -> impl Iterator<Item = (T, Coord<T>)> {
+    let zero = T::zero();
+    let side = move |c: Coord<T>| dir.0 * c.y - dir.1 * c.x;
+    let along = move |c: Coord<T>| dir.0 * c.x + dir.1 * c.y;
+
+    lines.into_iter().filter_map(move |line| {
+        let (a, b) = (line.start - pt, line.end - pt);
+        let (side_a, side_b) = (side(a), side(b));
+        if (side_a <= zero) == (side_b <= zero) {
+            return None;
+        }
+        let s = side_a / (side_a - side_b);
+        let hit = along(a) + s * (along(b) - along(a));
+        if hit > zero {
+            Some((hit, line.start + (line.end - line.start) * s))
+        } else {
+            None
+        }
+    })
+}
+
+fn half_open_crossings<T: CoordFloat, I: IntoIterator<Item = Line<T>>>(
+    pt: &Coord<T>,
+    lines: I,
+    dir: (T, T),
+) -> usize {
+    half_open_hits(*pt, lines, dir).count()
+}
+
+/// Crossing points of `half_open_hits`, nearest first
+fn sorted_hits<T: CoordFloat, I: IntoIterator<Item = Line<T>>>(
+    pt: &Coord<T>,
+    lines: I,
+    dir: (T, T),
+) -> Vec<Coord<T>> {
+    let mut hits = half_open_hits(*pt, lines, dir).collect::<Vec<_>>();
+    hits.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+    hits.into_iter().map(|(_, hit)| hit).collect()
+}
+
+/// Index of the nearest segment among `lines` crossed by `half_open_hits` along +X
+fn first_hit_index<T: CoordFloat, I: IntoIterator<Item = Line<T>>>(
+    pt: &Coord<T>,
+    lines: I,
+) -> Option<usize> {
+    let dir = (T::one(), T::zero());
+    lines
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            half_open_hits(*pt, core::iter::once(line), dir)
+                .next()
+                .map(|(hit, _)| (index, hit))
+        })
+        .fold(None, |best: Option<(usize, T)>, candidate| match best {
+            Some((_, hit)) if hit <= candidate.1 => best,
+            _ => Some(candidate),
+        })
+        .map(|(index, _)| index)
+}
+
+/// Parity of the crossings along the first non-degenerate direction, if any
+fn robust_in_ring<T: CoordFloat>(pt: &Coord<T>, ring: &LineString<T>) -> Option<bool> {
+    DIRECTIONS.iter().find_map(|&(x, y)| {
+        let dir = (T::from(x)?, T::from(y)?);
+        ray_crossings(pt, ring_lines(ring), dir).map(|count| count % 2 == 1)
+    })
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> RobustRayCasting<T, P> for LineString<T> {
+    fn within_robust(&self, pt: &P) -> bool {
+        let coord = (*pt).into();
+        robust_in_ring(&coord, self).unwrap_or_else(|| self.within(&coord))
+    }
+
+    fn within_with_ray(&self, pt: &P, ray_dir: (T, T)) -> bool {
+        half_open_crossings(&(*pt).into(), ring_lines(self), ray_dir) % 2 == 1
+    }
+
+    fn ray_intersections(&self, origin: &P, dir: (T, T)) -> Vec<Coord<T>> {
+        sorted_hits(&(*origin).into(), ring_lines(self), dir)
+    }
+
+    fn first_crossing_edge(&self, pt: &P) -> Option<usize> {
+        first_hit_index(&(*pt).into(), ring_lines(self))
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> RobustRayCasting<T, P> for Polygon<T> {
+    fn within_robust(&self, pt: &P) -> bool {
+        let coord = (*pt).into();
+        self.exterior().within_robust(&coord)
+            && !self
+                .interiors()
+                .iter()
+                .any(|ring| ring.within_robust(&coord))
+    }
+
+    fn within_with_ray(&self, pt: &P, ray_dir: (T, T)) -> bool {
+        let coord = (*pt).into();
+        self.exterior().within_with_ray(&coord, ray_dir)
+            && !self
+                .interiors()
+                .iter()
+                .any(|ring| ring.within_with_ray(&coord, ray_dir))
+    }
+
+    fn ray_intersections(&self, origin: &P, dir: (T, T)) -> Vec<Coord<T>> {
+        sorted_hits(
+            &(*origin).into(),
+            ring_lines(self.exterior()).chain(self.interiors().iter().flat_map(ring_lines)),
+            dir,
+        )
+    }
+
+    /// Exterior segments come first, then the ones of each hole in turn.
+    fn first_crossing_edge(&self, pt: &P) -> Option<usize> {
+        first_hit_index(
+            &(*pt).into(),
+            ring_lines(self.exterior()).chain(self.interiors().iter().flat_map(ring_lines)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RobustRayCasting;
+    use crate::{
+        testutil::{assert_agree, grid},
+        RayCasting,
+    };
+
+    use geo_types::{Coord, LineString, Polygon};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        (x, y).into()
+    }
+
+    #[test]
+    fn robust_comb() {
+        // every vertex of the upper side of the comb lies either at y = 5 or y = 10
+        let poly_comb: LineString<f64> = vec![
+            (0.0, 0.0),
+            (20.0, 0.0),
+            (20.0, 5.0),
+            (20.0, 10.0),
+            (16.0, 5.0),
+            (12.0, 10.0),
+            (8.0, 5.0),
+            (4.0, 10.0),
+            (0.0, 5.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        // the +X ray passes through 3 vertices, the half-open convention already handles them
+        assert!(poly_comb.within(&p(2.0, 5.0)));
+        assert!(poly_comb.within_robust(&p(2.0, 5.0)));
+        assert!(poly_comb.within_robust(&p(10.0, 5.0)));
+        assert!(poly_comb.within_robust(&p(12.0, 9.0)));
+        assert!(!poly_comb.within_robust(&p(8.0, 6.0)));
+        assert!(!poly_comb.within_robust(&p(-2.0, 5.0)));
+        assert!(!poly_comb.within_robust(&p(-2.0, 10.0)));
+        assert!(!poly_comb.within_robust(&p(22.0, 5.0)));
+
+        // off the boundary both always agree, vertex aligned points included
+        let poly_comb = Polygon::new(poly_comb, vec![]);
+        assert_agree(&poly_comb, &grid(&poly_comb, 0.5));
+    }
+
+    #[test]
+    fn ray_intersections_square() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert_eq!(
+            poly_square.ray_intersections(&p(5.0, 5.0), (1.0, 0.0)),
+            vec![p(10.0, 5.0)]
+        );
+        assert_eq!(
+            poly_square.ray_intersections(&p(-5.0, 5.0), (2.0, 0.0)),
+            vec![p(0.0, 5.0), p(10.0, 5.0)]
+        );
+        assert_eq!(
+            poly_square.ray_intersections(&p(5.0, 5.0), (0.0, -1.0)),
+            vec![p(5.0, 0.0)]
+        );
+        assert!(poly_square
+            .ray_intersections(&p(15.0, 5.0), (1.0, 0.0))
+            .is_empty());
+        assert!(poly_square
+            .ray_intersections(&p(5.0, 5.0), (0.0, 0.0))
+            .is_empty());
+
+        let poly_square_hole = Polygon::new(
+            poly_square,
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        assert_eq!(
+            poly_square_hole.ray_intersections(&p(-1.0, 5.0), (1.0, 0.0)),
+            vec![p(0.0, 5.0), p(2.5, 5.0), p(7.5, 5.0), p(10.0, 5.0)]
+        );
+        for pt in [p(1.0, 1.0), p(5.0, 5.0), p(1.0, 5.0), p(-1.0, 3.0)] {
+            let dir = (1.0, 0.5);
+            assert_eq!(
+                poly_square_hole.ray_intersections(&pt, dir).len() % 2 == 1,
+                poly_square_hole.within(&pt)
+            );
+        }
+    }
+
+    #[test]
+    fn robust_hexagon() {
+        let poly_hexagon: LineString<f64> = vec![
+            (3.0, 0.0),
+            (7.0, 0.0),
+            (10.0, 5.0),
+            (7.0, 10.0),
+            (3.0, 10.0),
+            (0.0, 5.0),
+            (3.0, 0.0),
+        ]
+        .into();
+        assert!(poly_hexagon.within_robust(&p(5.0, 5.0)));
+        assert!(poly_hexagon.within_robust(&p(5.0, 8.0)));
+        assert!(!poly_hexagon.within_robust(&p(-10.0, 5.0)));
+        assert!(!poly_hexagon.within_robust(&p(-2.0, 10.0)));
+    }
+
+    #[test]
+    fn robust_square_hole() {
+        let poly_square_hole = Polygon::new(
+            LineString::from(vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ]),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        assert!(!poly_square_hole.within_robust(&p(5.0, 5.0)));
+        assert!(poly_square_hole.within_robust(&p(5.0, 8.0)));
+        assert!(poly_square_hole.within_robust(&p(1.0, 2.5)));
+        assert!(!poly_square_hole.within_robust(&p(-10.0, 5.0)));
+    }
+
+    #[test]
+    fn with_ray_strange() {
+        let poly_strange: LineString<f64> = vec![
+            (0.0, 0.0),
+            (2.5, 2.5),
+            (0.0, 10.0),
+            (2.5, 7.5),
+            (7.5, 7.5),
+            (10.0, 10.0),
+            (10.0, 0.0),
+            (2.5, 2.5),
+        ]
+        .into();
+        assert!(poly_strange.within_with_ray(&p(5.0, 5.0), (1.0, 0.0)));
+        assert!(poly_strange.within_with_ray(&p(5.0, 5.0), (0.0, 1.0)));
+        assert!(!poly_strange.within_with_ray(&p(5.0, 8.0), (-1.0, -3.0)));
+        assert!(!poly_strange.within_with_ray(&p(5.0, 5.0), (0.0, 0.0)));
+        for x in -4..=24 {
+            for y in -4..=24 {
+                // the offsets keep the points off the boundary
+                let pt = p(f64::from(x) * 0.5 + 0.01, f64::from(y) * 0.5 + 0.03);
+                let expected = poly_strange.within(&pt);
+                assert_eq!(poly_strange.within_with_ray(&pt, (0.0, 1.0)), expected);
+                assert_eq!(poly_strange.within_with_ray(&pt, (-2.0, 1.0)), expected);
+                assert_eq!(poly_strange.within_with_ray(&pt, (-1.0, -3.0)), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn with_ray_matches_within() {
+        let poly_square_hole = Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+            ])],
+        );
+        for x in -4..=24 {
+            for y in -4..=24 {
+                let pt = p(f64::from(x) * 0.5, f64::from(y) * 0.5);
+                assert_eq!(
+                    poly_square_hole.within_with_ray(&pt, (1.0, 0.0)),
+                    poly_square_hole.within(&pt),
+                    "{:?}",
+                    pt
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn first_crossing_edge_square() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        // the right edge, from (10, 0) to (10, 10)
+        assert_eq!(poly_square.first_crossing_edge(&p(5.0, 5.0)), Some(1));
+        assert_eq!(poly_square.first_crossing_edge(&p(-5.0, 5.0)), Some(3));
+        assert_eq!(poly_square.first_crossing_edge(&p(15.0, 5.0)), None);
+        assert_eq!(poly_square.first_crossing_edge(&p(5.0, 15.0)), None);
+
+        let poly_square_hole = Polygon::new(
+            poly_square,
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        assert_eq!(poly_square_hole.first_crossing_edge(&p(1.0, 5.0)), Some(7));
+        assert_eq!(poly_square_hole.first_crossing_edge(&p(5.0, 5.0)), Some(5));
+        assert_eq!(poly_square_hole.first_crossing_edge(&p(8.0, 5.0)), Some(1));
+    }
+}
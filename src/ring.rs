@@ -0,0 +1,128 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Helpers treating line strings as rings
+
+use alloc::vec::Vec;
+
+use geo_types::{Coord, CoordNum, Line, LineString};
+
+use crate::ring_lines;
+
+/// Extension trait for line strings used as polygon rings
+pub trait Ring<T: CoordNum> {
+    /// Segments of the ring, including the closing one when the ring isn't explicitly closed
+    ///
+    /// This is how every ring is read by this crate, so an open ring with `n` coordinates yields
+    /// `n` segments, as many as the same ring explicitly closed.
+    fn closed_lines(&self) -> impl Iterator<Item = Line<T>> + '_;
+
+    /// Reverses the ring in place, flipping its orientation
+    ///
+    /// An explicitly closed ring stays closed, an open one stays open and is still closed
+    /// virtually, so the ring covers the same area either way.
+    fn reverse_ring(&mut self);
+}
+
+/// Builds a ring from its vertices, repeating the first one at the end when it isn't already
+///
+/// Rings are closed virtually anyway, but other tools expect them explicitly closed.
+pub fn ring_from_points<T: CoordNum>(pts: Vec<Coord<T>>) -> LineString<T> {
+    let mut ring = LineString(pts);
+    ring.close();
+    ring
+}
+
+impl<T: CoordNum> Ring<T> for LineString<T> {
+    fn closed_lines(&self) -> impl Iterator<Item = Line<T>> + '_ {
+        ring_lines(self)
+    }
+
+    fn reverse_ring(&mut self) {
+        self.0.reverse();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ring_from_points, Ring};
+    use crate::{Area, RayCasting};
+
+    use geo_types::{Coord, Line, LineString};
+
+    #[test]
+    fn closed_lines_open() {
+        let open: LineString<f64> = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+        let lines = open.closed_lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[3], Line::new((0.0, 10.0), (0.0, 0.0)));
+
+        let mut closed = open.clone();
+        closed.close();
+        assert_eq!(closed.closed_lines().collect::<Vec<_>>(), lines);
+    }
+
+    #[test]
+    fn closed_lines_short() {
+        assert_eq!(LineString::<i32>(vec![]).closed_lines().count(), 0);
+        assert_eq!(LineString::from(vec![(1, 1)]).closed_lines().count(), 0);
+        let lines = LineString::from(vec![(0, 0), (1, 1)])
+            .closed_lines()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![Line::new((0, 0), (1, 1)), Line::new((1, 1), (0, 0))]
+        );
+    }
+
+    #[test]
+    fn reverse_ring() {
+        let square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        let mut reversed = square.clone();
+        reversed.reverse_ring();
+        assert!(reversed.is_closed());
+        assert_eq!(reversed.signed_area(), -square.signed_area());
+        assert_eq!(reversed.0[1], Coord::from((0.0, 10.0)));
+        assert!(reversed.within(&Coord::from((5.0, 5.0))));
+        reversed.reverse_ring();
+        assert_eq!(reversed, square);
+
+        let open: LineString<f64> = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)].into();
+        let mut reversed = open.clone();
+        reversed.reverse_ring();
+        assert!(!reversed.is_closed());
+        assert_eq!(reversed.closed_lines().count(), 4);
+        assert_eq!(reversed.signed_area(), -open.signed_area());
+        reversed.reverse_ring();
+        assert_eq!(reversed, open);
+    }
+
+    #[test]
+    fn ring_from_corners() {
+        let corners = vec![
+            Coord::from((0.0, 0.0)),
+            Coord::from((10.0, 0.0)),
+            Coord::from((10.0, 10.0)),
+            Coord::from((0.0, 10.0)),
+        ];
+        let ring = ring_from_points(corners.clone());
+        assert_eq!(ring.0.len(), 5);
+        assert!(ring.is_closed());
+        assert_eq!(ring.0[4], corners[0]);
+        assert!(ring.within(&Coord::from((5.0, 5.0))));
+
+        assert_eq!(ring_from_points(ring.0.clone()), ring);
+        assert_eq!(ring_from_points::<f64>(vec![]).0.len(), 0);
+    }
+}
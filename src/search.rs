@@ -0,0 +1,247 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Queries over slices of polygons
+
+use alloc::vec::Vec;
+
+use geo_types::{Coord, CoordFloat, Polygon};
+
+use crate::{BoundaryDistance, RayCasting};
+
+/// Returns the index of the first polygon containing a point, if any
+pub fn locate_in<T: CoordFloat>(pt: &Coord<T>, polygons: &[Polygon<T>]) -> Option<usize> {
+    polygons.iter().position(|poly| poly.within(pt))
+}
+
+/// Returns the first polygon containing a point, if any, borrowed from `polygons`
+pub fn find_container<'a, T: CoordFloat>(
+    pt: &Coord<T>,
+    polygons: &'a [Polygon<T>],
+) -> Option<&'a Polygon<T>> {
+    polygons.iter().find(|poly| poly.within(pt))
+}
+
+/// Returns the index of the polygon closest to a point, along with its distance from the
+/// polygon's boundary
+///
+/// The first polygon containing the point wins with a zero distance, as `locate_in` would find it.
+/// Otherwise the closest one wins, the first of them on ties. Polygons with no boundary are
+/// skipped, so `None` is returned only when none is left.
+pub fn nearest_polygon<T: CoordFloat>(
+    pt: &Coord<T>,
+    polygons: &[Polygon<T>],
+) -> Option<(usize, T)> {
+    if let Some(index) = locate_in(pt, polygons) {
+        return Some((index, T::zero()));
+    }
+    polygons
+        .iter()
+        .map(|poly| poly.signed_distance(pt))
+        .enumerate()
+        .filter(|(_, distance)| distance.is_finite())
+        .fold(None, |best, candidate| match best {
+            Some((_, distance)) if distance <= candidate.1 => best,
+            _ => Some(candidate),
+        })
+}
+
+/// Returns the indices of every polygon containing a point, for overlapping zones
+pub fn locate_all_in<T: CoordFloat>(pt: &Coord<T>, polygons: &[Polygon<T>]) -> Vec<usize> {
+    polygons
+        .iter()
+        .enumerate()
+        .filter(|(_, poly)| poly.within(pt))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Buckets points by containing polygon, returning the indices of the points within each polygon
+///
+/// The result is aligned index-for-index with `polygons`, and each bucket lists point indices in
+/// increasing order. Points within several overlapping polygons go in all of their buckets, points
+/// outside every polygon in none.
+pub fn partition_by_polygon<T: CoordFloat>(
+    pts: &[Coord<T>],
+    polygons: &[Polygon<T>],
+) -> Vec<Vec<usize>> {
+    polygons
+        .iter()
+        .map(|poly| {
+            pts.iter()
+                .enumerate()
+                .filter(|(_, pt)| poly.within(*pt))
+                .map(|(index, _)| index)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_container, locate_all_in, locate_in, nearest_polygon, partition_by_polygon};
+    use crate::BoundaryDistance;
+
+    use geo_types::{Coord, LineString, Polygon};
+
+    fn cells() -> Vec<Polygon<f64>> {
+        vec![
+            Polygon::new(
+                LineString::from(vec![
+                    (45.3563321662796, 11.9147053956319),
+                    (45.4293499926637, 11.9455630525467),
+                    (45.4392542159797, 11.8515426867682),
+                    (45.3661863570488, 11.8209138798751),
+                ]),
+                vec![],
+            ),
+            Polygon::new(
+                LineString::from(vec![
+                    (45.4293499926637, 11.9455630525467),
+                    (45.5024707283596, 11.9765478474091),
+                    (45.5124252464723, 11.8822977972565),
+                    (45.4392542159797, 11.8515426867682),
+                ]),
+                vec![],
+            ),
+            Polygon::new(
+                LineString::from(vec![
+                    (45.3661863570488, 11.8209138798751),
+                    (45.4392542159797, 11.8515426867682),
+                    (45.4490695215551, 11.7576024308158),
+                    (45.3759520538385, 11.7272026072339),
+                ]),
+                vec![],
+            ),
+        ]
+    }
+
+    fn points() -> Vec<Coord<f64>> {
+        vec![
+            (45.429671680421, 11.887047957258).into(),
+            (45.412408636479, 11.866946356603).into(),
+            (45.390711713006, 11.868550140008).into(),
+            (45.421928106575, 11.897589742744).into(),
+            (45.414838131946, 11.811773142492).into(),
+            (45.41341604488, 11.802568326636).into(),
+            (45.395726701315, 11.833525908467).into(),
+        ]
+    }
+
+    #[test]
+    fn locate_in_cells() {
+        let cells = cells();
+        let found = points()
+            .iter()
+            .map(|pt| locate_in(pt, &cells))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(2),
+                Some(2),
+                Some(0)
+            ]
+        );
+        assert_eq!(locate_in(&(0.0, 0.0).into(), &cells), None);
+        assert_eq!(locate_in(&(0.0, 0.0).into(), &[]), None);
+    }
+
+    #[test]
+    fn find_container_cells() {
+        let cells = cells();
+        let points = points();
+        let point1 = find_container(&points[0], &cells).unwrap();
+        assert!(core::ptr::eq(point1, &cells[0]));
+        assert!(core::ptr::eq(
+            find_container(&points[4], &cells).unwrap(),
+            &cells[2]
+        ));
+        assert_eq!(find_container(&(0.0, 0.0).into(), &cells), None);
+    }
+
+    #[test]
+    fn nearest_polygon_cells() {
+        let cells = cells();
+        let point5 = points()[4];
+        assert_eq!(nearest_polygon(&point5, &cells), Some((2, 0.0)));
+
+        // outside cell1 and cell2, but right next to the edge cell1 shares with cell3
+        let (index, distance) = nearest_polygon(&point5, &cells[..2]).unwrap();
+        assert_eq!(index, 0);
+        assert!(distance > 0.0);
+        assert_eq!(distance, cells[0].signed_distance(&point5));
+        assert!(distance < cells[1].signed_distance(&point5));
+
+        let empty = Polygon::new(LineString(vec![]), vec![]);
+        assert_eq!(
+            nearest_polygon(&point5, core::slice::from_ref(&empty)),
+            None
+        );
+        assert_eq!(
+            nearest_polygon(&point5, &[empty, cells[1].clone()]).map(|(index, _)| index),
+            Some(1)
+        );
+        assert_eq!(nearest_polygon(&point5, &[]), None);
+    }
+
+    #[test]
+    fn locate_all_in_overlapping() {
+        let squares = vec![
+            Polygon::new(
+                LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.)]),
+                vec![],
+            ),
+            Polygon::new(
+                LineString::from(vec![(5., 5.), (15., 5.), (15., 15.), (5., 15.)]),
+                vec![],
+            ),
+        ];
+        assert_eq!(locate_all_in(&(7.5, 7.5).into(), &squares), vec![0, 1]);
+        assert_eq!(locate_all_in(&(2.5, 2.5).into(), &squares), vec![0]);
+        assert_eq!(locate_all_in(&(12.5, 12.5).into(), &squares), vec![1]);
+        assert!(locate_all_in(&(20., 20.).into(), &squares).is_empty());
+
+        let cells = cells();
+        let found = points()
+            .iter()
+            .map(|pt| locate_all_in(pt, &cells))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                vec![0],
+                vec![0],
+                vec![0],
+                vec![0],
+                vec![2],
+                vec![2],
+                vec![0]
+            ]
+        );
+    }
+
+    #[test]
+    fn partition_by_polygon_cells() {
+        let cells = cells();
+        let mut points = points();
+        points.push((0.0, 0.0).into());
+        assert_eq!(
+            partition_by_polygon(&points, &cells),
+            vec![vec![0, 1, 2, 3, 6], vec![], vec![4, 5]]
+        );
+        assert!(partition_by_polygon(&points, &[]).is_empty());
+        assert_eq!(
+            partition_by_polygon(&[], &cells),
+            vec![Vec::<usize>::new(); 3]
+        );
+    }
+}
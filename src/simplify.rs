@@ -0,0 +1,203 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Ring simplification bounded by the containment tolerance
+
+use alloc::{vec, vec::Vec};
+
+use geo_types::{Coord, CoordFloat, Line, LineString, Polygon};
+
+use crate::segment_distance;
+
+/// Trait reducing the number of vertices, without changing the containment of points away from
+/// the boundary
+pub trait Simplify<T: CoordFloat> {
+    /// Copy simplified with the Douglas-Peucker algorithm, keeping the vertices further than
+    /// `tolerance` from the simplified boundary
+    ///
+    /// The simplified boundary stays within `tolerance` of the original one, so
+    /// `RayCasting::within` gives the same result for every point further than `tolerance` from
+    /// the original boundary, while points within `tolerance` of it may change. Rings that would
+    /// collapse to fewer than 3 vertices are kept as they are, but this doesn't prevent two rings
+    /// closer than `tolerance` from crossing once simplified.
+    fn simplify_preserving(&self, tolerance: T) -> Self;
+}
+
+/// Marks the vertices of `coords` between its endpoints to keep
+fn douglas_peucker<T: CoordFloat>(coords: &[Coord<T>], tolerance: T, keep: &mut [bool]) {
+    if coords.len() < 3 {
+        return;
+    }
+    let last = coords.len() - 1;
+    let line = Line::new(coords[0], coords[last]);
+    let (index, distance) = coords[1..last]
+        .iter()
+        .map(|c| segment_distance(c, &line))
+        .enumerate()
+        .fold((0, T::zero()), |best, (index, distance)| {
+            if distance > best.1 {
+                (index + 1, distance)
+            } else {
+                best
+            }
+        });
+    if distance > tolerance {
+        keep[index] = true;
+        douglas_peucker(&coords[..=index], tolerance, &mut keep[..=index]);
+        douglas_peucker(&coords[index..], tolerance, &mut keep[index..]);
+    }
+}
+
+fn simplify_ring<T: CoordFloat>(ring: &LineString<T>, tolerance: T) -> LineString<T> {
+    let mut vertices = ring.0.clone();
+    let closed = ring.0.len() > 1 && ring.is_closed();
+    if closed {
+        vertices.pop();
+    }
+    if vertices.len() < 4 {
+        return ring.clone();
+    }
+
+    // split the ring at the vertex furthest from the first one, both halves are then simplified
+    // as open paths
+    let first = vertices[0];
+    let (split, _) = vertices
+        .iter()
+        .enumerate()
+        .map(|(index, c)| (index, (c.x - first.x).hypot(c.y - first.y)))
+        .fold((0, T::zero()), |best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        });
+    vertices.push(first);
+    let mut keep = vec![false; vertices.len()];
+    keep[0] = true;
+    keep[split] = true;
+    douglas_peucker(&vertices[..=split], tolerance, &mut keep[..=split]);
+    douglas_peucker(&vertices[split..], tolerance, &mut keep[split..]);
+    vertices.pop();
+
+    let mut coords = vertices
+        .into_iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(c, _)| c)
+        .collect::<Vec<_>>();
+    if coords.len() < 3 {
+        return ring.clone();
+    }
+    if closed {
+        coords.push(first);
+    }
+    LineString(coords)
+}
+
+impl<T: CoordFloat> Simplify<T> for LineString<T> {
+    fn simplify_preserving(&self, tolerance: T) -> Self {
+        simplify_ring(self, tolerance)
+    }
+}
+
+impl<T: CoordFloat> Simplify<T> for Polygon<T> {
+    fn simplify_preserving(&self, tolerance: T) -> Self {
+        Polygon::new(
+            self.exterior().simplify_preserving(tolerance),
+            self.interiors()
+                .iter()
+                .map(|ring| ring.simplify_preserving(tolerance))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Simplify;
+    use crate::{BoundaryDistance, RayCasting};
+
+    use geo_types::{Coord, LineString, Polygon};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        Coord { x, y }
+    }
+
+    /// `poly_square` with a vertex every 0.5 along its edges, slightly off the straight line
+    fn dense_square() -> LineString<f64> {
+        let jitter = |i: i32| if i % 2 == 0 { 0.01 } else { -0.01 };
+        let mut coords = Vec::new();
+        for i in 0..20 {
+            coords.push(p(f64::from(i) * 0.5, if i == 0 { 0.0 } else { jitter(i) }));
+        }
+        for i in 0..20 {
+            coords.push(p(
+                10.0 + if i == 0 { 0.0 } else { jitter(i) },
+                f64::from(i) * 0.5,
+            ));
+        }
+        for i in 0..20 {
+            coords.push(p(
+                10.0 - f64::from(i) * 0.5,
+                10.0 + if i == 0 { 0.0 } else { jitter(i) },
+            ));
+        }
+        for i in 0..20 {
+            coords.push(p(
+                if i == 0 { 0.0 } else { jitter(i) },
+                10.0 - f64::from(i) * 0.5,
+            ));
+        }
+        coords.push(p(0.0, 0.0));
+        LineString(coords)
+    }
+
+    #[test]
+    fn simplify_square() {
+        let dense = dense_square();
+        let simplified = dense.simplify_preserving(0.1);
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert_eq!(simplified, poly_square);
+
+        // nothing is dropped when every vertex matters
+        assert_eq!(dense.simplify_preserving(0.001), dense);
+
+        let poly = Polygon::new(dense.clone(), vec![]);
+        let simplified = poly.simplify_preserving(0.1);
+        for x in -8..=48 {
+            for y in -8..=48 {
+                let pt = p(f64::from(x) * 0.25, f64::from(y) * 0.25);
+                if poly.signed_distance(&pt).abs() > 0.1 {
+                    assert_eq!(simplified.within(&pt), poly.within(&pt), "{:?}", pt);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn simplify_collapsing() {
+        // a thin sliver would collapse to a segment, it's left alone
+        let sliver: LineString<f64> =
+            vec![(0.0, 0.0), (5.0, 0.01), (10.0, 0.0), (5.0, -0.01)].into();
+        assert_eq!(sliver.simplify_preserving(0.1), sliver);
+        let triangle: LineString<f64> = vec![(0.0, 0.0), (10.0, 0.0), (5.0, 5.0)].into();
+        assert_eq!(triangle.simplify_preserving(100.0), triangle);
+        let open: LineString<f64> = vec![(0.0, 0.0), (5.0, 0.0), (10.0, 0.0), (10.0, 10.0)].into();
+        assert_eq!(
+            open.simplify_preserving(0.1),
+            LineString::from(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)])
+        );
+    }
+}
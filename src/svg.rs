@@ -0,0 +1,175 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! SVG rendering, to inspect surprising containment results
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::Write;
+
+use geo_types::{Coord, CoordFloat, LineString, Polygon};
+
+use crate::{bounding_rect, RayCasting};
+
+/// Trait rendering a polygonal area as an SVG document, for debugging purposes
+pub trait ToSvg<T: CoordFloat> {
+    /// Renders every ring as a `<polygon>` element, holes filled white over the exterior, and
+    /// `query` as a `<circle>`, green when `RayCasting::within` reports it inside and red
+    /// otherwise
+    ///
+    /// The Y axis points up, like in the geometry, and the view box encloses both the rings and
+    /// the query point with a small margin.
+    fn to_svg(&self, query: Option<&Coord<T>>) -> String;
+}
+
+/// Vertices of a ring as an SVG `points` attribute, without the closing coordinate
+fn points<T: CoordFloat>(ring: &LineString<T>) -> String {
+    let coords = match ring.0.split_last() {
+        Some((last, rest)) if !rest.is_empty() && Some(last) == ring.0.first() => rest,
+        _ => &ring.0[..],
+    };
+    coords
+        .iter()
+        .map(|c| format!("{},{}", float(c.x), float(c.y)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn float<T: CoordFloat>(value: T) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+fn render<T: CoordFloat>(
+    exterior: &LineString<T>,
+    holes: &[LineString<T>],
+    query: Option<(&Coord<T>, bool)>,
+) -> String {
+    let rect = bounding_rect(
+        exterior
+            .coords()
+            .chain(holes.iter().flat_map(|ring| ring.coords()))
+            .chain(query.map(|(pt, _)| pt)),
+    );
+    let (min, max) = rect.map_or(((0., 0.), (1., 1.)), |rect| {
+        (
+            (float(rect.min().x), float(rect.min().y)),
+            (float(rect.max().x), float(rect.max().y)),
+        )
+    });
+    let margin = (max.0 - min.0).max(max.1 - min.1).max(1.) * 0.05;
+    let (width, height) = (max.0 - min.0 + 2. * margin, max.1 - min.1 + 2. * margin);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+        min.0 - margin,
+        -max.1 - margin,
+        width,
+        height
+    );
+    svg.push_str("<g transform=\"scale(1,-1)\">");
+    let style = "stroke=\"black\" vector-effect=\"non-scaling-stroke\"";
+    // writing to a String never fails
+    let _ = write!(
+        svg,
+        "<polygon points=\"{}\" fill=\"lightblue\" {}/>",
+        points(exterior),
+        style
+    );
+    for hole in holes {
+        let _ = write!(
+            svg,
+            "<polygon points=\"{}\" fill=\"white\" {}/>",
+            points(hole),
+            style
+        );
+    }
+    if let Some((pt, within)) = query {
+        let _ = write!(
+            svg,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>",
+            float(pt.x),
+            float(pt.y),
+            margin / 2.,
+            if within { "green" } else { "red" }
+        );
+    }
+    svg.push_str("</g></svg>");
+    svg
+}
+
+impl<T: CoordFloat> ToSvg<T> for LineString<T> {
+    fn to_svg(&self, query: Option<&Coord<T>>) -> String {
+        render(self, &[], query.map(|pt| (pt, self.within(pt))))
+    }
+}
+
+impl<T: CoordFloat> ToSvg<T> for Polygon<T> {
+    fn to_svg(&self, query: Option<&Coord<T>>) -> String {
+        render(
+            self.exterior(),
+            self.interiors(),
+            query.map(|pt| (pt, self.within(pt))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ToSvg;
+
+    use geo_types::{Coord, LineString, Polygon};
+
+    fn square() -> LineString<f64> {
+        vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into()
+    }
+
+    fn polygon_points(svg: &str) -> Vec<usize> {
+        svg.split("<polygon points=\"")
+            .skip(1)
+            .map(|rest| rest[..rest.find('"').unwrap()].split(' ').count())
+            .collect()
+    }
+
+    #[test]
+    fn svg_square() {
+        let svg = square().to_svg(None);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(polygon_points(&svg), vec![4]);
+        assert!(svg.contains("0,0 10,0 10,10 0,10\""));
+        assert!(!svg.contains("<circle"));
+
+        let inside = square().to_svg(Some(&Coord { x: 5.0, y: 5.0 }));
+        assert!(inside.contains("cx=\"5\" cy=\"5\""));
+        assert!(inside.contains("fill=\"green\""));
+        let outside = square().to_svg(Some(&Coord { x: 15.0, y: 5.0 }));
+        assert!(outside.contains("fill=\"red\""));
+    }
+
+    #[test]
+    fn svg_square_hole() {
+        let poly_square_hole = Polygon::new(
+            square(),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        let svg = poly_square_hole.to_svg(Some(&Coord { x: 5.0, y: 5.0 }));
+        assert_eq!(polygon_points(&svg), vec![4, 4]);
+        assert!(svg.contains("fill=\"red\""));
+    }
+}
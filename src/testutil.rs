@@ -0,0 +1,49 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Helpers shared by the tests of several modules
+
+use geo_types::{Coord, Polygon};
+
+use crate::{bounding_rect, BoundaryDistance, RayCasting, RobustRayCasting};
+
+/// Asserts that `RayCasting::within` and `RobustRayCasting::within_robust` agree on every point
+///
+/// They only differ in how they break ties on rays hitting vertices and on boundary points, so
+/// they must agree on any point off the boundary.
+pub(crate) fn assert_agree(poly: &Polygon<f64>, pts: &[Coord<f64>]) {
+    for pt in pts {
+        assert_eq!(
+            poly.within(pt),
+            poly.within_robust(pt),
+            "{:?} in {:?}",
+            pt,
+            poly
+        );
+    }
+}
+
+/// Points of a grid `step` wide covering `poly` and its surroundings, skipping the ones on its
+/// boundary, where the two tie-breaking rules legitimately differ
+pub(crate) fn grid(poly: &Polygon<f64>, step: f64) -> Vec<Coord<f64>> {
+    let rect = bounding_rect(poly.exterior().coords()).expect("empty exterior");
+    let (min, max) = (
+        rect.min() - (step, step).into(),
+        rect.max() + (step, step).into(),
+    );
+    let columns = ((max.x - min.x) / step).round() as i32;
+    let rows = ((max.y - min.y) / step).round() as i32;
+    (0..=columns)
+        .flat_map(|i| {
+            (0..=rows).map(move |j| Coord {
+                x: min.x + f64::from(i) * step,
+                y: min.y + f64::from(j) * step,
+            })
+        })
+        .filter(|pt| poly.signed_distance(pt).abs() > 1e-9)
+        .collect()
+}
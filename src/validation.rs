@@ -0,0 +1,291 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Geometry validity checks
+
+use alloc::vec::Vec;
+
+use geo_types::{Coord, CoordFloat, Line, LineString, Polygon};
+
+use crate::{edges, is_degenerate, pt_in_polygon, segments_intersect, RayCastError};
+
+/// Validity checks for ray casting inputs
+pub trait Validation<T: CoordFloat> {
+    /// Checks that no ring is degenerate, having fewer than 3 distinct points or only collinear
+    /// ones
+    ///
+    /// Degenerate rings enclose no area, `RayCasting::within` reports no point inside them.
+    fn is_valid(&self) -> bool;
+
+    /// Checks that no two non-adjacent segments intersect, while adjacent ones only share their
+    /// common vertex
+    ///
+    /// Self-intersecting rings make the even-odd rule of `RayCasting::within` disagree with the
+    /// intuitive area, `Winding` is better suited for them. Every pair of segments is tested, so
+    /// this is quadratic in the number of coordinates.
+    fn is_simple(&self) -> bool;
+
+    /// Checks that every hole lies inside the exterior ring, without crossing or touching it,
+    /// and that no two holes overlap
+    ///
+    /// Ring orientation is ignored, holes wound like the exterior are accepted as `within` does.
+    /// Empty holes are skipped, every pair of segments across rings is tested.
+    fn validate_rings(&self) -> Result<(), RayCastError>;
+}
+
+/// Checks if `pt` lies on `line`
+fn touches<T: CoordFloat>(pt: Coord<T>, line: &Line<T>) -> bool {
+    segments_intersect(line, &Line::new(pt, pt))
+}
+
+/// Checks if two segments of the same ring intersect, other than at the vertex adjacent ones share
+fn ring_crossing<T: CoordFloat>(lines: &[Line<T>], i: usize, j: usize) -> bool {
+    let (first, second) = (&lines[i], &lines[j]);
+    if j == i + 1 || (i == 0 && j == lines.len() - 1) {
+        // adjacent segments only overlap when one folds back along the other
+        let shared = if first.end == second.start || first.end == second.end {
+            first.end
+        } else {
+            first.start
+        };
+        let far = |line: &Line<T>| {
+            if line.start == shared {
+                line.end
+            } else {
+                line.start
+            }
+        };
+        touches(far(second), first) || touches(far(first), second)
+    } else {
+        segments_intersect(first, second)
+    }
+}
+
+/// Checks if any segment of `first` shares a point with any segment of `second`
+fn rings_intersect<T: CoordFloat>(first: &[Line<T>], second: &[Line<T>]) -> bool {
+    first
+        .iter()
+        .any(|line| second.iter().any(|other| segments_intersect(line, other)))
+}
+
+fn simple_rings<T: CoordFloat>(rings: &[&LineString<T>]) -> bool {
+    let rings = rings
+        .iter()
+        .map(|ring| edges(ring).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    rings.iter().enumerate().all(|(index, lines)| {
+        let within =
+            (0..lines.len()).all(|i| (i + 1..lines.len()).all(|j| !ring_crossing(lines, i, j)));
+        let across = rings[index + 1..].iter().all(|others| {
+            lines
+                .iter()
+                .all(|line| others.iter().all(|other| !segments_intersect(line, other)))
+        });
+        within && across
+    })
+}
+
+impl<T: CoordFloat> Validation<T> for LineString<T> {
+    fn is_valid(&self) -> bool {
+        !is_degenerate(&self.0)
+    }
+
+    fn is_simple(&self) -> bool {
+        simple_rings(&[self])
+    }
+
+    /// A lone ring has no holes, so it always passes.
+    fn validate_rings(&self) -> Result<(), RayCastError> {
+        Ok(())
+    }
+}
+
+/// A polygon is valid when its exterior and every hole are.
+impl<T: CoordFloat> Validation<T> for Polygon<T> {
+    fn is_valid(&self) -> bool {
+        self.exterior().is_valid() && self.interiors().iter().all(Validation::is_valid)
+    }
+
+    /// Rings must not touch each other either.
+    fn is_simple(&self) -> bool {
+        let rings = core::iter::once(self.exterior())
+            .chain(self.interiors())
+            .collect::<Vec<_>>();
+        simple_rings(&rings)
+    }
+
+    fn validate_rings(&self) -> Result<(), RayCastError> {
+        let exterior = edges(self.exterior()).collect::<Vec<_>>();
+        let holes = self
+            .interiors()
+            .iter()
+            .filter(|ring| !ring.0.is_empty())
+            .map(|ring| (ring, edges(ring).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        for (index, (ring, lines)) in holes.iter().enumerate() {
+            if rings_intersect(lines, &exterior) {
+                return Err(RayCastError::CrossingRings);
+            }
+            // with no shared point the hole is either fully inside or fully outside
+            if !pt_in_polygon(&ring.0[0], self.exterior()) {
+                return Err(RayCastError::HoleOutsideExterior);
+            }
+            for (other, other_lines) in &holes[index + 1..] {
+                if rings_intersect(lines, other_lines)
+                    || pt_in_polygon(&ring.0[0], other)
+                    || pt_in_polygon(&other.0[0], ring)
+                {
+                    return Err(RayCastError::OverlappingHoles);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Validation;
+    use crate::{PreparedPolygon, RayCastError, RayCasting};
+
+    use geo_types::{Coord, LineString, Polygon};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        Coord { x, y }
+    }
+
+    fn grid() -> impl Iterator<Item = Coord<f64>> {
+        (-40..=80)
+            .flat_map(|i| (-40..=80).map(move |j| p(f64::from(i) * 0.25, f64::from(j) * 0.25)))
+    }
+
+    #[test]
+    fn degenerate_rings() {
+        let rings: Vec<LineString<f64>> = vec![
+            vec![(0., 0.), (5., 0.), (10., 0.), (0., 0.)].into(),
+            vec![(0., 0.), (5., 5.), (10., 10.), (0., 0.)].into(),
+            vec![(0., 0.), (5., 5.)].into(),
+            vec![(1., 1.), (1., 1.), (1., 1.)].into(),
+            LineString(vec![]),
+        ];
+        for ring in rings {
+            assert!(!ring.is_valid());
+            let poly = Polygon::new(ring.clone(), vec![]);
+            assert!(!poly.is_valid());
+            let prepared = PreparedPolygon::new(&poly);
+            for pt in grid() {
+                assert!(!ring.within(&pt), "{:?} in {:?}", pt, ring);
+                assert!(!prepared.within(&pt), "{:?} in {:?}", pt, ring);
+            }
+        }
+    }
+
+    #[test]
+    fn valid_rings() {
+        let square: LineString<f64> = vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.)].into();
+        assert!(square.is_valid());
+        let triangle: LineString<f64> = vec![(0., 0.), (5., 0.), (10., 0.), (5., 5.)].into();
+        assert!(triangle.is_valid());
+
+        let degenerate_hole = Polygon::new(
+            square.clone(),
+            vec![vec![(2., 2.), (4., 4.), (6., 6.)].into()],
+        );
+        assert!(!degenerate_hole.is_valid());
+        assert!(degenerate_hole.within(&p(4., 4.)));
+        assert!(PreparedPolygon::new(&degenerate_hole).within(&p(4., 4.)));
+        assert!(Polygon::new(square, vec![]).is_valid());
+    }
+
+    #[test]
+    fn simple_rings() {
+        let square: LineString<f64> = vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.)].into();
+        assert!(square.is_simple());
+        let mut closed = square.clone();
+        closed.close();
+        closed.0.insert(1, (0., 0.).into());
+        assert!(closed.is_simple());
+
+        let poly_strange: LineString<f64> = vec![
+            (0.0, 0.0),
+            (2.5, 2.5),
+            (0.0, 10.0),
+            (2.5, 7.5),
+            (7.5, 7.5),
+            (10.0, 10.0),
+            (10.0, 0.0),
+            (2.5, 2.5),
+        ]
+        .into();
+        assert!(!poly_strange.is_simple());
+
+        let bowtie: LineString<f64> = vec![(0., 0.), (10., 10.), (10., 0.), (0., 10.)].into();
+        assert!(!bowtie.is_simple());
+        // a spike folding back along the previous segment
+        let spike: LineString<f64> = vec![(0., 0.), (10., 0.), (5., 0.), (5., 5.)].into();
+        assert!(!spike.is_simple());
+        let back_and_forth: LineString<f64> = vec![(0., 0.), (10., 0.)].into();
+        assert!(!back_and_forth.is_simple());
+        assert!(LineString::<f64>(vec![]).is_simple());
+    }
+
+    #[test]
+    fn simple_polygons() {
+        let square: LineString<f64> = vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.)].into();
+        let hole = |min: f64, max: f64| -> LineString<f64> {
+            vec![(min, min), (max, min), (max, max), (min, max)].into()
+        };
+        assert!(Polygon::new(square.clone(), vec![hole(2.5, 7.5)]).is_simple());
+        assert!(!Polygon::new(square.clone(), vec![hole(5., 15.)]).is_simple());
+        assert!(!Polygon::new(square.clone(), vec![hole(0., 5.)]).is_simple());
+        assert!(!Polygon::new(square, vec![hole(2., 4.), hole(3., 6.)]).is_simple());
+    }
+
+    #[test]
+    fn validate_rings() {
+        let square: LineString<f64> = vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.)].into();
+        let hole = |min: f64, max: f64| -> LineString<f64> {
+            vec![(min, min), (max, min), (max, max), (min, max)].into()
+        };
+        let validate =
+            |holes: Vec<LineString<f64>>| Polygon::new(square.clone(), holes).validate_rings();
+        assert!(square.validate_rings().is_ok());
+        assert!(validate(vec![]).is_ok());
+        // wound the same way as the exterior
+        assert!(validate(vec![hole(2.5, 7.5)]).is_ok());
+        let mut reversed = hole(2.5, 7.5);
+        reversed.0.reverse();
+        assert!(validate(vec![reversed]).is_ok());
+        assert!(validate(vec![hole(1., 2.), hole(3., 4.)]).is_ok());
+        assert!(validate(vec![LineString(vec![])]).is_ok());
+
+        assert!(matches!(
+            validate(vec![hole(5., 15.)]),
+            Err(RayCastError::CrossingRings)
+        ));
+        assert!(matches!(
+            validate(vec![hole(0., 5.)]),
+            Err(RayCastError::CrossingRings)
+        ));
+        assert!(matches!(
+            validate(vec![hole(20., 30.)]),
+            Err(RayCastError::HoleOutsideExterior)
+        ));
+        assert!(matches!(
+            validate(vec![hole(2., 4.), hole(3., 6.)]),
+            Err(RayCastError::OverlappingHoles)
+        ));
+        assert!(matches!(
+            validate(vec![hole(2., 8.), hole(4., 6.)]),
+            Err(RayCastError::OverlappingHoles)
+        ));
+        assert!(matches!(
+            validate(vec![hole(4., 6.), hole(2., 8.)]),
+            Err(RayCastError::OverlappingHoles)
+        ));
+    }
+}
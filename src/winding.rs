@@ -0,0 +1,192 @@
+// Copyright 2019 Marco Napetti
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Winding number algorithm, implementing the nonzero fill rule
+
+use geo_types::{Coord, CoordFloat, Line, LineString, Polygon};
+
+use crate::ring_lines;
+
+/// Trait implementing the winding number algorithm
+///
+/// `RayCasting::within` implements the even-odd rule: a point is inside when a ray cast from it
+/// crosses the boundary an odd number of times. The nonzero rule instead sums the crossings with
+/// their direction, +1 for upward segments and -1 for downward ones, and a point is inside when
+/// the sum is not zero. The two rules agree on simple rings, but differ where a self-intersecting
+/// ring overlaps itself: the overlapping area is outside for even-odd, and inside for nonzero when
+/// both overlapping parts wind the same way.
+pub trait Winding<T: CoordFloat, P: Into<Coord<T>>> {
+    /// Checks if a point is within a polygonal area under the nonzero rule
+    fn within_winding(&self, pt: &P) -> bool;
+
+    /// Signed count of the turns the boundary makes around a point, positive when
+    /// counterclockwise
+    ///
+    /// 0 means outside under the nonzero rule and ±1 a simple containment, while larger values
+    /// tell regions covered several times. For polygons this sums every ring, so a hole cancels
+    /// the exterior only when wound the opposite way, as `Orientation::normalized` makes them.
+    fn winding_number(&self, pt: &P) -> i32;
+}
+
+fn is_left<T: CoordFloat>(pt: &Coord<T>, line: &Line<T>) -> T {
+    line.dx() * (pt.y - line.start.y) - (pt.x - line.start.x) * line.dy()
+}
+
+fn winding_number<T: CoordFloat, I: IntoIterator<Item = Line<T>>>(pt: &Coord<T>, lines: I) -> i32 {
+    lines.into_iter().fold(0, |wn, line| {
+        if line.start.y <= pt.y {
+            if line.end.y > pt.y && is_left(pt, &line) > T::zero() {
+                return wn + 1;
+            }
+        } else if line.end.y <= pt.y && is_left(pt, &line) < T::zero() {
+            return wn - 1;
+        }
+        wn
+    })
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> Winding<T, P> for LineString<T> {
+    fn within_winding(&self, pt: &P) -> bool {
+        winding_number(&(*pt).into(), ring_lines(self)) != 0
+    }
+
+    fn winding_number(&self, pt: &P) -> i32 {
+        winding_number(&(*pt).into(), ring_lines(self))
+    }
+}
+
+impl<T: CoordFloat, P: Into<Coord<T>> + Copy> Winding<T, P> for Polygon<T> {
+    fn within_winding(&self, pt: &P) -> bool {
+        let coord = (*pt).into();
+        winding_number(&coord, ring_lines(self.exterior())) != 0
+            && !self
+                .interiors()
+                .iter()
+                .any(|ring| winding_number(&coord, ring_lines(ring)) != 0)
+    }
+
+    fn winding_number(&self, pt: &P) -> i32 {
+        let coord = (*pt).into();
+        self.interiors()
+            .iter()
+            .chain(core::iter::once(self.exterior()))
+            .map(|ring| winding_number(&coord, ring_lines(ring)))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Winding;
+    use crate::RayCasting;
+
+    use geo_types::{Coord, LineString, Point, Polygon};
+
+    fn p(x: f64, y: f64) -> Coord<f64> {
+        (x, y).into()
+    }
+
+    #[test]
+    fn winding_square() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert!(poly_square.within_winding(&p(5.0, 5.0)));
+        assert!(poly_square.within_winding(&Point::new(5.0, 8.0)));
+        assert!(!poly_square.within_winding(&p(-10.0, 5.0)));
+        assert!(!poly_square.within_winding(&p(15.0, 5.0)));
+    }
+
+    #[test]
+    fn winding_number_square() {
+        let poly_square: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert_eq!(poly_square.winding_number(&p(5.0, 5.0)), 1);
+        assert_eq!(poly_square.winding_number(&p(15.0, 5.0)), 0);
+        let reversed = LineString(poly_square.0.iter().rev().copied().collect());
+        assert_eq!(reversed.winding_number(&Point::new(5.0, 5.0)), -1);
+        assert_eq!(reversed.winding_number(&p(-10.0, 5.0)), 0);
+    }
+
+    #[test]
+    fn winding_square_hole() {
+        let poly_square_hole = Polygon::new(
+            LineString::from(vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ]),
+            vec![LineString::from(vec![
+                (2.5, 2.5),
+                (7.5, 2.5),
+                (7.5, 7.5),
+                (2.5, 7.5),
+                (2.5, 2.5),
+            ])],
+        );
+        assert!(!poly_square_hole.within_winding(&p(5.0, 5.0)));
+        assert!(poly_square_hole.within_winding(&p(5.0, 8.0)));
+        assert!(!poly_square_hole.within_winding(&p(-10.0, 5.0)));
+
+        // both rings are counterclockwise, the hole adds up instead of cancelling
+        assert_eq!(poly_square_hole.winding_number(&p(5.0, 5.0)), 2);
+        assert_eq!(poly_square_hole.winding_number(&p(5.0, 8.0)), 1);
+    }
+
+    #[test]
+    fn winding_strange() {
+        let poly_strange: LineString<f64> = vec![
+            (0.0, 0.0),
+            (2.5, 2.5),
+            (0.0, 10.0),
+            (2.5, 7.5),
+            (7.5, 7.5),
+            (10.0, 10.0),
+            (10.0, 0.0),
+            (2.5, 2.5),
+        ]
+        .into();
+        assert!(poly_strange.within_winding(&p(5.0, 5.0)));
+        assert!(poly_strange.within_winding(&p(8.0, 5.0)));
+        assert!(!poly_strange.within_winding(&p(5.0, 8.0)));
+        assert!(!poly_strange.within_winding(&p(-10.0, 5.0)));
+        assert!(!poly_strange.within_winding(&p(1.0, 5.0)));
+    }
+
+    #[test]
+    fn winding_overlap() {
+        // the same square wound twice, the whole area is covered two times
+        let poly_twice: LineString<f64> = vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        assert!(!poly_twice.within(&p(5.0, 5.0)));
+        assert!(poly_twice.within_winding(&p(5.0, 5.0)));
+        assert_eq!(poly_twice.winding_number(&p(5.0, 5.0)), 2);
+    }
+}